@@ -0,0 +1,49 @@
+use std::fs;
+use std::path::Path;
+
+use crate::{Error, Stage};
+
+
+/// Known answers of a day, recorded in its `answers.toml` as one table per stage
+/// mapping each input file name to the expected answer:
+///
+/// ```toml
+/// [stage1]
+/// "test.txt" = 24000
+/// ```
+pub struct Answers {
+    table: toml::Table,
+}
+
+impl Answers {
+
+    pub fn load<P>(path: P) -> Result<Answers, Error>
+    where
+        P: AsRef<Path>
+    {
+        let content = fs::read_to_string(path)?;
+        let table = content.parse().map_err(|error: toml::de::Error| {
+            Error::InvalidAnswers(error.message().to_string())
+        })?;
+        Ok(Answers { table })
+    }
+
+    /// Input file names having a recorded answer for `stage`.
+    pub fn inputs(&self, stage: Stage) -> Vec<&str> {
+        self.stage_table(stage)
+            .map(|inputs| inputs.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// The recorded answer, as displayed by [`crate::Answer`].
+    pub fn expected(&self, stage: Stage, input: &str) -> Option<String> {
+        match self.stage_table(stage)?.get(input)? {
+            toml::Value::String(text) => Some(text.clone()),
+            value => Some(value.to_string()),
+        }
+    }
+
+    fn stage_table(&self, stage: Stage) -> Option<&toml::Table> {
+        self.table.get(&stage.to_string())?.as_table()
+    }
+}
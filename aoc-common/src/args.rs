@@ -0,0 +1,44 @@
+use crate::{Error, Format, Stage};
+
+
+/// The `<stage> <path> [--format text|json] [--bench N]` arguments shared by every
+/// day binary, where `<path>` may be `-` to read the standard input.
+pub struct Args {
+    pub stage: Stage,
+    pub path: String,
+    pub format: Format,
+    pub bench: Option<usize>,
+}
+
+impl Args {
+
+    pub fn from_env() -> Result<Args, Error> {
+        Self::from_args(std::env::args().skip(1))
+    }
+
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<Args, Error> {
+        let mut positionals = Vec::new();
+        let mut format = Format::Text;
+        let mut bench = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => {
+                    format = args.next().ok_or(Error::MissingArgument("an output format"))?.parse()?;
+                }
+                "--bench" => {
+                    let runs = args.next().ok_or(Error::MissingArgument("a number of runs"))?;
+                    bench = Some(runs.parse().map_err(|_| Error::InvalidArgument(runs))?);
+                }
+                _ => positionals.push(arg),
+            }
+        }
+
+        let mut positionals = positionals.into_iter();
+        let stage = positionals.next().ok_or(Error::MissingArgument("puzzle stage"))?;
+        let path = positionals.next().ok_or(Error::MissingArgument("a file name"))?;
+        if let Some(extra) = positionals.next() {
+            return Err(Error::InvalidArgument(extra));
+        }
+        Ok(Args { stage: stage.parse()?, path, format, bench })
+    }
+}
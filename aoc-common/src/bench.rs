@@ -0,0 +1,102 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::{lines_from_str, Answer, Error, Solver, Stage};
+
+
+/// Durations of one phase over every benchmark run.
+pub struct Timings {
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+
+    fn new(mut samples: Vec<Duration>) -> Timings {
+        samples.sort();
+        Timings { samples }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.first().copied().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        self.samples.get(self.samples.len() / 2).copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.last().copied().unwrap_or_default()
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "min_ms": self.min().as_secs_f64() * 1000.0,
+            "median_ms": self.median().as_secs_f64() * 1000.0,
+            "max_ms": self.max().as_secs_f64() * 1000.0,
+        })
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "min {:>10.3?}  median {:>10.3?}  max {:>10.3?}", self.min(), self.median(), self.max())
+    }
+}
+
+
+pub struct Bench {
+    pub runs: usize,
+    pub answer: Answer,
+    pub parse: Timings,
+    pub solve: Timings,
+}
+
+impl Bench {
+
+    /// Parses and solves `input` `runs` times, timing both phases separately.
+    /// Reading the input is left out of the measure.
+    pub fn run<S: Solver + ?Sized>(solver: &S, stage: Stage, input: &str, runs: usize) -> Result<Bench, Error> {
+        let mut answer = None;
+        let mut parse_samples = Vec::with_capacity(runs);
+        let mut solve_samples = Vec::with_capacity(runs);
+        for _ in 0..runs.max(1) {
+            let start = Instant::now();
+            let parsed = solver.parse(stage, lines_from_str(input))?;
+            parse_samples.push(start.elapsed());
+
+            let start = Instant::now();
+            let result = match stage {
+                Stage::One => solver.part1(parsed)?,
+                Stage::Two => solver.part2(parsed)?,
+            };
+            solve_samples.push(start.elapsed());
+            answer = Some(result);
+        }
+
+        Ok(Bench {
+            runs: parse_samples.len(),
+            answer: answer.ok_or("No benchmark run")?,
+            parse: Timings::new(parse_samples),
+            solve: Timings::new(solve_samples),
+        })
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "runs": self.runs,
+            "answer": self.answer.to_json(),
+            "parse": self.parse.to_json(),
+            "solve": self.solve.to_json(),
+        })
+    }
+}
+
+impl fmt::Display for Bench {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} runs, answer {}", self.runs, self.answer)?;
+        writeln!(f, "parse  {}", self.parse)?;
+        write!(f, "solve  {}", self.solve)
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+use std::ops::Range;
+
+
+/// The reason why a line of input was rejected. Each day lists its own reasons
+/// in an enum, optionally pointing at the offending bytes of the line.
+pub trait Reason: std::error::Error + Send + Sync + 'static {
+    fn span(&self) -> Option<Range<usize>> {
+        None
+    }
+}
+
+
+/// A rejected input line, rendered like a compiler diagnostic:
+///
+/// ```text
+/// invalid move id 'Q'
+///  --> day02/input.txt:3:3
+///   |
+/// 3 | A Q
+///   |   ^
+/// ```
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub path: Option<String>,
+    pub line: usize,
+    pub span: Option<Range<usize>>,
+    pub text: String,
+    pub reason: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl Diagnostic {
+
+    /// `line` is 1-based, as displayed to the user.
+    pub fn new(line: usize, text: &str, reason: impl Reason) -> Diagnostic {
+        Diagnostic {
+            path: None,
+            line,
+            span: reason.span(),
+            text: String::from(text),
+            reason: Box::new(reason),
+        }
+    }
+
+    pub fn in_file(mut self, path: &str) -> Diagnostic {
+        self.path = Some(String::from(path));
+        self
+    }
+
+    /// Moves the diagnostic down by `count` lines, for sections parsed on their own.
+    pub fn shifted(mut self, count: usize) -> Diagnostic {
+        self.line += count;
+        self
+    }
+
+    pub fn column(&self) -> usize {
+        let start = self.span.as_ref().map_or(0, |span| span.start);
+        self.text.get(..start).map_or(start, |prefix| prefix.chars().count()) + 1
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path.as_deref().unwrap_or("<input>");
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{}", self.reason)?;
+        writeln!(f, "{gutter}--> {path}:{}:{}", self.line, self.column())?;
+        writeln!(f, "{gutter} |")?;
+        write!(f, "{} | {}", self.line, self.text)?;
+        if let Some(span) = &self.span {
+            let width = self.text.get(span.clone()).map_or(1, |s| s.chars().count().max(1));
+            write!(f, "\n{gutter} | {}{}", " ".repeat(self.column() - 1), "^".repeat(width))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Diagnostic {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.reason.as_ref())
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt;
+use std::io;
+
+use crate::Diagnostic;
+
+
+pub enum Error {
+    Io(io::Error),
+    MissingArgument(&'static str),
+    InvalidArgument(String),
+    UnknownCommand(String),
+    UnknownDay(u32),
+    UnknownStage(String),
+    InvalidAnswers(String),
+    Scaffold(&'static str),
+    Puzzle(&'static str),
+    Parse(Diagnostic),
+    InvalidLines(Vec<Diagnostic>),
+    Solver(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Unable to read the file: {error}"),
+            Self::MissingArgument(what) => write!(f, "Expecting {what}"),
+            Self::InvalidArgument(arg) => write!(f, "Invalid argument: {arg}"),
+            Self::UnknownCommand(command) => write!(f, "Unknown command: {command}"),
+            Self::UnknownDay(day) => write!(f, "No solver registered for day {day}"),
+            Self::UnknownStage(stage) => write!(f, "Unknown stage: {stage}"),
+            Self::InvalidAnswers(message) => write!(f, "Invalid answers file: {message}"),
+            Self::Scaffold(message) => write!(f, "{message}"),
+            Self::Puzzle(message) => write!(f, "{message}"),
+            Self::Parse(diagnostic) => write!(f, "{diagnostic}"),
+            Self::InvalidLines(diagnostics) => {
+                for diagnostic in diagnostics {
+                    writeln!(f, "{diagnostic}\n")?;
+                }
+                write!(f, "{} invalid lines", diagnostics.len())
+            }
+            Self::Solver(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+// `main` reports its error through `Debug`, so keep it as readable as `Display`
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(diagnostic) => Some(diagnostic),
+            Self::InvalidLines(diagnostics) => diagnostics.first().map(|d| d as _),
+            Self::Solver(error) => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<Diagnostic> for Error {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Self::Parse(diagnostic)
+    }
+}
+
+impl From<Vec<Diagnostic>> for Error {
+    fn from(diagnostics: Vec<Diagnostic>) -> Self {
+        Self::InvalidLines(diagnostics)
+    }
+}
+
+impl From<&'static str> for Error {
+    fn from(message: &'static str) -> Self {
+        Self::Puzzle(message)
+    }
+}
@@ -0,0 +1,93 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+use crate::Error;
+
+
+/// Path standing for the standard input.
+pub const STDIN_PATH: &str = "-";
+
+/// Reads the whole file, or the standard input for [`STDIN_PATH`].
+pub fn read_input<P>(filename: P) -> Result<String, Error>
+where
+    P: AsRef<Path>
+{
+    if filename.as_ref() == Path::new(STDIN_PATH) {
+        let mut content = String::new();
+        io::stdin().lock().read_to_string(&mut content)?;
+        return Ok(content);
+    }
+    Ok(fs::read_to_string(filename)?)
+}
+
+/// Opens the file, or the standard input for [`STDIN_PATH`], to be read
+/// progressively rather than loaded in memory.
+pub fn open_input<P>(filename: P) -> Result<Box<dyn BufRead>, Error>
+where
+    P: AsRef<Path>
+{
+    if filename.as_ref() == Path::new(STDIN_PATH) {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    Ok(Box::new(BufReader::new(File::open(filename)?)))
+}
+
+/// Reads the file, or the standard input for [`STDIN_PATH`], one line at a
+/// time. Each I/O or encoding error is yielded where it occurs, see
+/// [`process_lines`] to hand the lines to a parser expecting plain strings.
+pub fn read_lines<P>(filename: P) -> Result<impl Iterator<Item = Result<String, Error>>, Error>
+where
+    P: AsRef<Path>
+{
+    Ok(lines_from_reader(open_input(filename)?))
+}
+
+pub fn lines_from_reader(reader: impl BufRead) -> impl Iterator<Item = Result<String, Error>> {
+    reader.lines().map(|line| Ok(line?))
+}
+
+/// Runs `parse` over the lines until the first error, which is then returned
+/// instead of whatever `parse` made of the truncated input.
+pub fn process_lines<I, T, F>(lines: I, parse: F) -> Result<T, Error>
+where
+    I: Iterator<Item = Result<String, Error>>,
+    F: FnOnce(ProcessLines<'_, I>) -> Result<T, Error>,
+{
+    let mut error = None;
+    let result = parse(ProcessLines { lines, error: &mut error });
+    match error {
+        Some(error) => Err(error),
+        None => result,
+    }
+}
+
+/// The lines handed out by [`process_lines`], ending at the first error.
+pub struct ProcessLines<'a, I> {
+    lines: I,
+    error: &'a mut Option<Error>,
+}
+
+impl<I> Iterator for ProcessLines<'_, I>
+where
+    I: Iterator<Item = Result<String, Error>>,
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.error.is_some() {
+            return None;
+        }
+        match self.lines.next()? {
+            Ok(line) => Some(line),
+            Err(error) => {
+                *self.error = Some(error);
+                None
+            }
+        }
+    }
+}
+
+pub fn lines_from_str(input: &str) -> impl Iterator<Item = String> + '_ {
+    input.lines().map(String::from)
+}
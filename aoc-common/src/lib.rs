@@ -0,0 +1,19 @@
+mod answers;
+mod args;
+mod bench;
+mod diagnostic;
+mod error;
+mod input;
+mod report;
+mod solver;
+mod stage;
+
+pub use answers::Answers;
+pub use args::Args;
+pub use bench::{Bench, Timings};
+pub use diagnostic::{Diagnostic, Reason};
+pub use error::Error;
+pub use input::{lines_from_reader, lines_from_str, open_input, process_lines, read_input, read_lines, ProcessLines, STDIN_PATH};
+pub use report::{run_day, run_day_with, Format, Report};
+pub use solver::{in_file, Answer, Solver};
+pub use stage::Stage;
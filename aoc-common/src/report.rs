@@ -0,0 +1,101 @@
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use crate::{Answer, Args, Error, Solver, Stage};
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(Error::InvalidArgument(String::from(s))),
+        }
+    }
+}
+
+
+impl Answer {
+
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Number(number) => json!(number),
+            Self::Text(text) => json!(text),
+            Self::List(answers) => answers.iter().map(Answer::to_json).collect(),
+        }
+    }
+}
+
+
+/// The outcome of one solver run, as printed by `--format json`.
+pub struct Report {
+    pub day: u32,
+    pub stage: Stage,
+    pub path: String,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+impl Report {
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "day": self.day,
+            "stage": self.stage.to_string(),
+            "answer": self.answer.to_json(),
+            "input": self.path,
+            "elapsed_ms": self.elapsed.as_secs_f64() * 1000.0,
+        })
+    }
+}
+
+
+/// Shared `main` of the day binaries: solves the stage given on the command line
+/// and prints either `sentence` or a JSON report.
+pub fn run_day<S: Solver>(
+    day: u32,
+    solver: &S,
+    sentence: impl Fn(Stage, &Answer) -> String,
+) -> Result<(), Error> {
+    run_day_with(day, solver, Args::from_env()?, sentence)
+}
+
+/// [`run_day`] with arguments already parsed, for days taking extra flags.
+pub fn run_day_with<S: Solver>(
+    day: u32,
+    solver: &S,
+    args: Args,
+    sentence: impl Fn(Stage, &Answer) -> String,
+) -> Result<(), Error> {
+    if let Some(runs) = args.bench {
+        let bench = solver.bench_file(args.stage, &args.path, runs)?;
+        match args.format {
+            Format::Text => println!("{bench}"),
+            Format::Json => println!("{}", bench.to_json()),
+        }
+        return Ok(());
+    }
+
+    let start = Instant::now();
+    let answer = solver.solve_file(args.stage, &args.path)?;
+    let elapsed = start.elapsed();
+
+    match args.format {
+        Format::Text => println!("{}", sentence(args.stage, &answer)),
+        Format::Json => {
+            let report = Report { day, stage: args.stage, path: args.path, answer, elapsed };
+            println!("{}", report.to_json());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use std::fmt;
+use std::io::BufRead;
+
+use crate::{lines_from_reader, lines_from_str, process_lines, read_input, read_lines, Bench, Error, Stage, STDIN_PATH};
+
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(u64),
+    Text(String),
+    List(Vec<Answer>),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Text(text) => write!(f, "{text}"),
+            Self::List(answers) => {
+                for (i, answer) in answers.iter().enumerate() {
+                    if i > 0 { write!(f, ",")?; }
+                    write!(f, "{answer}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(number: u32) -> Self {
+        Self::Number(number.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(number: u64) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(number: usize) -> Self {
+        Self::Number(number as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl<T: Into<Answer>> From<Vec<T>> for Answer {
+    fn from(answers: Vec<T>) -> Self {
+        Self::List(answers.into_iter().map(Into::into).collect())
+    }
+}
+
+
+/// A day's puzzle: the input is parsed once for the requested stage, then
+/// handed to the matching part which returns the answer instead of printing it.
+pub trait Solver {
+    type Input;
+
+    fn parse(&self, stage: Stage, lines: impl Iterator<Item = String>) -> Result<Self::Input, Error>;
+
+    fn part1(&self, input: Self::Input) -> Result<Answer, Error>;
+
+    fn part2(&self, input: Self::Input) -> Result<Answer, Error>;
+
+    fn solve(&self, stage: Stage, lines: impl Iterator<Item = String>) -> Result<Answer, Error> {
+        let input = self.parse(stage, lines)?;
+        match stage {
+            Stage::One => self.part1(input),
+            Stage::Two => self.part2(input),
+        }
+    }
+
+    fn solve_str(&self, stage: Stage, input: &str) -> Result<Answer, Error> {
+        self.solve(stage, lines_from_str(input))
+    }
+
+    fn solve_reader(&self, stage: Stage, reader: impl BufRead) -> Result<Answer, Error> {
+        process_lines(lines_from_reader(reader), |lines| self.solve(stage, lines))
+    }
+
+    /// `path` may be [`STDIN_PATH`] to read the puzzle input from the standard input.
+    fn solve_file(&self, stage: Stage, path: &str) -> Result<Answer, Error> {
+        process_lines(read_lines(path)?, |lines| self.solve(stage, lines)).map_err(|error| in_file(error, path))
+    }
+
+    fn bench_file(&self, stage: Stage, path: &str, runs: usize) -> Result<Bench, Error> {
+        let input = read_input(path)?;
+        Bench::run(self, stage, &input, runs).map_err(|error| in_file(error, path))
+    }
+}
+
+/// Points the diagnostics of `error` at the file `path` they were read from.
+pub fn in_file(error: Error, path: &str) -> Error {
+    let source = if path == STDIN_PATH { "<stdin>" } else { path };
+    match error {
+        Error::Parse(diagnostic) => Error::Parse(diagnostic.in_file(source)),
+        Error::InvalidLines(diagnostics) => {
+            Error::InvalidLines(diagnostics.into_iter().map(|d| d.in_file(source)).collect())
+        }
+        error => error,
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    One,
+    Two,
+}
+
+impl FromStr for Stage {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stage1" | "1" => Ok(Self::One),
+            "stage2" | "2" => Ok(Self::Two),
+            _ => Err(Error::UnknownStage(String::from(s))),
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::One => write!(f, "stage1"),
+            Self::Two => write!(f, "stage2"),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use aoc_common::{Error, Format, Stage};
+
+
+pub enum Command {
+    Run { day: u32, stage: Stage, path: String, format: Format, bench: Option<usize> },
+    /// `root` is the workspace directory, found from the current one when `None`.
+    RunAll { jobs: usize, format: Format, root: Option<PathBuf> },
+    New { day: u32, root: Option<PathBuf> },
+}
+
+impl Command {
+
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<Command, Error> {
+        let command = args.next().ok_or(Error::MissingArgument("a command"))?;
+        match command.as_str() {
+            "run" => Self::run_from_args(args),
+            "run-all" => Self::run_all_from_args(args),
+            "new" => Self::new_from_args(args),
+            _ => Err(Error::UnknownCommand(command)),
+        }
+    }
+
+    fn new_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, Error> {
+        let mut day = None;
+        let mut root = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--root" => {
+                    root = Some(PathBuf::from(args.next().ok_or(Error::MissingArgument("a workspace directory"))?));
+                }
+                _ if day.is_none() => day = Some(arg.parse().map_err(|_| Error::InvalidArgument(arg))?),
+                _ => return Err(Error::InvalidArgument(arg)),
+            }
+        }
+        Ok(Command::New { day: day.ok_or(Error::MissingArgument("a day number"))?, root })
+    }
+
+    fn run_all_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, Error> {
+        let mut jobs = 1;
+        let mut format = Format::Text;
+        let mut root = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--root" => {
+                    root = Some(PathBuf::from(args.next().ok_or(Error::MissingArgument("a workspace directory"))?));
+                }
+                "--jobs" => {
+                    let value = args.next().ok_or(Error::MissingArgument("a number of threads"))?;
+                    jobs = value.parse().map_err(|_| Error::InvalidArgument(value))?;
+                }
+                "--format" => {
+                    format = args.next().ok_or(Error::MissingArgument("an output format"))?.parse()?;
+                }
+                _ => return Err(Error::InvalidArgument(arg)),
+            }
+        }
+        Ok(Command::RunAll { jobs, format, root })
+    }
+
+    fn run_from_args(mut args: impl Iterator<Item = String>) -> Result<Command, Error> {
+        let mut day = None;
+        let mut stage = None;
+        let mut path = None;
+        let mut format = Format::Text;
+        let mut bench = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--day" => {
+                    let value = args.next().ok_or(Error::MissingArgument("a day number"))?;
+                    day = Some(value.parse().map_err(|_| Error::InvalidArgument(value))?);
+                }
+                "--stage" => {
+                    let value = args.next().ok_or(Error::MissingArgument("a stage"))?;
+                    stage = Some(value.parse()?);
+                }
+                "--format" => {
+                    format = args.next().ok_or(Error::MissingArgument("an output format"))?.parse()?;
+                }
+                "--bench" => {
+                    let runs = args.next().ok_or(Error::MissingArgument("a number of runs"))?;
+                    bench = Some(runs.parse().map_err(|_| Error::InvalidArgument(runs))?);
+                }
+                _ if path.is_none() => path = Some(arg),
+                _ => return Err(Error::InvalidArgument(arg)),
+            }
+        }
+
+        Ok(Command::Run {
+            day: day.ok_or(Error::MissingArgument("--day"))?,
+            stage: stage.ok_or(Error::MissingArgument("--stage"))?,
+            path: path.ok_or(Error::MissingArgument("a file name"))?,
+            format,
+            bench,
+        })
+    }
+}
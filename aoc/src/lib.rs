@@ -0,0 +1,3 @@
+pub mod registry;
+pub mod run_all;
+pub mod workspace;
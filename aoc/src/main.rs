@@ -0,0 +1,71 @@
+mod cli;
+mod scaffold;
+
+use std::path::PathBuf;
+use std::time::Instant;
+
+use aoc_common::{Error, Format, Report};
+
+use aoc::registry;
+use aoc::run_all::{print_table, run_all, Status};
+use aoc::workspace;
+use cli::Command;
+
+
+/// The `--root` given, or else the workspace containing the current directory.
+fn workspace_root(root: Option<PathBuf>) -> Result<PathBuf, Error> {
+    match root {
+        Some(root) => Ok(root),
+        None => Ok(workspace::root(&std::env::current_dir()?)),
+    }
+}
+
+fn main() -> Result<(), Error> {
+    match Command::from_args(std::env::args().skip(1))? {
+        Command::Run { day, stage, path, format, bench: Some(runs) } => {
+            let day = registry::find(day).ok_or(Error::UnknownDay(day))?;
+            let bench = (day.bench)(stage, &path, runs)?;
+            match format {
+                Format::Text => println!("{bench}"),
+                Format::Json => println!("{}", bench.to_json()),
+            }
+            Ok(())
+        }
+        Command::Run { day, stage, path, format, bench: None } => {
+            let day = registry::find(day).ok_or(Error::UnknownDay(day))?;
+            let start = Instant::now();
+            let answer = (day.solve)(stage, &path)?;
+            let elapsed = start.elapsed();
+            match format {
+                Format::Text => println!("{answer}"),
+                Format::Json => {
+                    let report = Report { day: day.number, stage, path, answer, elapsed };
+                    println!("{}", report.to_json());
+                }
+            }
+            Ok(())
+        }
+        Command::RunAll { jobs, format, root } => {
+            let outcomes = run_all(&workspace_root(root)?, jobs);
+            match format {
+                Format::Text => print_table(&outcomes),
+                Format::Json => {
+                    let reports: Vec<_> = outcomes.iter().map(|outcome| outcome.to_json()).collect();
+                    println!("{}", serde_json::Value::from(reports));
+                }
+            }
+            if outcomes.iter().any(|outcome| matches!(outcome.status(), Status::Fail | Status::Error)) {
+                return Err(Error::Puzzle("Some answers do not match the recorded ones"));
+            }
+            Ok(())
+        }
+        Command::New { day, root } => {
+            if registry::find(day).is_some() {
+                return Err(Error::Scaffold("This day already exists"));
+            }
+            let crate_name = scaffold::new_day(&workspace_root(root)?, day)?;
+            println!("Created {crate_name}, put the puzzle inputs in {crate_name}/test.txt and {crate_name}/input.txt");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use aoc_common::{Answer, Bench, Error, Solver, Stage};
+
+
+pub struct Day {
+    pub number: u32,
+    pub directory: &'static str,
+    pub solve: fn(Stage, &str) -> Result<Answer, Error>,
+    pub bench: fn(Stage, &str, usize) -> Result<Bench, Error>,
+}
+
+macro_rules! day {
+    ($number:expr, $krate:ident) => {
+        Day {
+            number: $number,
+            directory: stringify!($krate),
+            solve: |stage, path| $krate::Puzzle.solve_file(stage, path),
+            bench: |stage, path, runs| $krate::Puzzle.bench_file(stage, path, runs),
+        }
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(1, day01),
+    day!(2, day02),
+    day!(3, day03),
+    day!(4, day04),
+    day!(5, day05),
+    day!(6, day06),
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
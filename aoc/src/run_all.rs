@@ -0,0 +1,128 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+
+use aoc_common::{Answer, Answers, Error, Stage};
+
+use crate::registry::{Day, DAYS};
+
+
+pub const INPUT_FILE: &str = "input.txt";
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Unrecorded,
+    Error,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail => write!(f, "FAIL"),
+            Self::Unrecorded => write!(f, "?"),
+            Self::Error => write!(f, "ERROR"),
+        }
+    }
+}
+
+
+pub struct Outcome {
+    pub day: &'static Day,
+    pub stage: Stage,
+    pub answer: Result<Answer, Error>,
+    pub expected: Option<String>,
+    pub elapsed: Duration,
+}
+
+impl Outcome {
+
+    pub fn status(&self) -> Status {
+        match (&self.answer, &self.expected) {
+            (Err(_), _) => Status::Error,
+            (Ok(_), None) => Status::Unrecorded,
+            (Ok(answer), Some(expected)) if answer.to_string() == *expected => Status::Pass,
+            (Ok(_), Some(_)) => Status::Fail,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let (answer, error) = match &self.answer {
+            Ok(answer) => (answer.to_json(), Value::Null),
+            Err(error) => (Value::Null, json!(error.to_string())),
+        };
+        json!({
+            "day": self.day.number,
+            "stage": self.stage.to_string(),
+            "answer": answer,
+            "expected": self.expected,
+            "error": error,
+            "status": self.status().to_string(),
+            "elapsed_ms": self.elapsed.as_secs_f64() * 1000.0,
+        })
+    }
+}
+
+
+fn run_one(root: &Path, day: &'static Day, stage: Stage) -> Outcome {
+    let directory = root.join(day.directory);
+    let expected = Answers::load(directory.join("answers.toml"))
+        .ok()
+        .and_then(|answers| answers.expected(stage, INPUT_FILE));
+    let path = directory.join(INPUT_FILE);
+    let start = Instant::now();
+    let answer = (day.solve)(stage, &path.to_string_lossy());
+    Outcome { day, stage, answer, expected, elapsed: start.elapsed() }
+}
+
+/// Runs both stages of every registered day on its `input.txt`, on `jobs` threads.
+/// Outcomes come back in day and stage order whatever the number of threads.
+pub fn run_all(root: &Path, jobs: usize) -> Vec<Outcome> {
+    let tasks: Vec<(&'static Day, Stage)> = DAYS.iter()
+        .flat_map(|day| [(day, Stage::One), (day, Stage::Two)])
+        .collect();
+    let outcomes: Vec<Mutex<Option<Outcome>>> = tasks.iter().map(|_| Mutex::new(None)).collect();
+    let next_task = AtomicUsize::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, tasks.len()) {
+            scope.spawn(|| {
+                loop {
+                    let index = next_task.fetch_add(1, Ordering::Relaxed);
+                    let Some((day, stage)) = tasks.get(index) else { break };
+                    let outcome = run_one(root, day, *stage);
+                    *outcomes[index].lock().expect("Poisoned outcome") = Some(outcome);
+                }
+            });
+        }
+    });
+
+    outcomes.into_iter()
+        .filter_map(|outcome| outcome.into_inner().expect("Poisoned outcome"))
+        .collect()
+}
+
+pub fn print_table(outcomes: &[Outcome]) {
+    println!("{:>3}  {:<6}  {:<20}  {:>12}  status", "day", "stage", "answer", "time");
+    for outcome in outcomes {
+        let answer = match &outcome.answer {
+            Ok(answer) => answer.to_string(),
+            Err(error) => error.to_string().lines().next().unwrap_or_default().to_string(),
+        };
+        println!(
+            "{:>3}  {:<6}  {:<20}  {:>12.3?}  {}",
+            outcome.day.number, outcome.stage.to_string(), answer, outcome.elapsed, outcome.status()
+        );
+    }
+    let total: Duration = outcomes.iter().map(|outcome| outcome.elapsed).sum();
+    let passed = outcomes.iter().filter(|outcome| outcome.status() == Status::Pass).count();
+    println!("{passed}/{} passed in {total:.3?}", outcomes.len());
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::Error;
+
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.template");
+const LIB_RS: &str = include_str!("../templates/lib.rs.template");
+const MAIN_RS: &str = include_str!("../templates/main.rs.template");
+const ANSWERS_TOML: &str = include_str!("../templates/answers.toml.template");
+
+
+fn render(template: &str, crate_name: &str, number: u32) -> String {
+    template
+        .replace("{{crate}}", crate_name)
+        .replace("{{number}}", &number.to_string())
+}
+
+/// Inserts `line` right after the last line of `content` starting with `prefix`.
+fn insert_after_last(content: &str, prefix: &str, line: &str) -> Result<String, Error> {
+    let lines: Vec<&str> = content.lines().collect();
+    let position = lines.iter()
+        .rposition(|l| l.trim_start().starts_with(prefix))
+        .ok_or(Error::Scaffold("Unable to find where to register the new day"))?;
+    let mut result: Vec<&str> = lines[..=position].to_vec();
+    result.push(line);
+    result.extend(&lines[position + 1..]);
+    Ok(result.join("\n") + "\n")
+}
+
+/// The new content of the file at `path` once `line` is registered in it, or
+/// `None` when it already is.
+fn register(path: &Path, prefix: &str, line: &str) -> Result<Option<(PathBuf, String)>, Error> {
+    let content = fs::read_to_string(path)?;
+    if content.contains(line.trim()) {
+        return Ok(None);
+    }
+    Ok(Some((path.to_path_buf(), insert_after_last(&content, prefix, line)?)))
+}
+
+/// Generates the `dayNN` crate in the workspace at `root` and registers it in
+/// the workspace members, the runner dependencies and the registry. Every
+/// registration is prepared before writing anything, so that a workspace which
+/// cannot take the new day is left untouched.
+pub fn new_day(root: &Path, number: u32) -> Result<String, Error> {
+    let crate_name = format!("day{number:02}");
+    let directory = root.join(&crate_name);
+    if directory.exists() {
+        return Err(Error::Scaffold("The day directory already exists"));
+    }
+    let registrations = [
+        register(&root.join("Cargo.toml"), "\"day", &format!("    \"{crate_name}\","))?,
+        register(
+            &root.join("aoc/Cargo.toml"),
+            "day",
+            &format!("{crate_name} = {{ path = \"../{crate_name}\" }}"),
+        )?,
+        register(
+            &root.join("aoc/src/registry.rs"),
+            "day!(",
+            &format!("    day!({number}, {crate_name}),"),
+        )?,
+    ];
+
+    fs::create_dir(&directory)?;
+    fs::create_dir(directory.join("src"))?;
+    fs::write(directory.join("Cargo.toml"), render(CARGO_TOML, &crate_name, number))?;
+    fs::write(directory.join("src/lib.rs"), render(LIB_RS, &crate_name, number))?;
+    fs::write(directory.join("src/main.rs"), render(MAIN_RS, &crate_name, number))?;
+    fs::write(directory.join("answers.toml"), ANSWERS_TOML)?;
+    fs::write(directory.join("test.txt"), "")?;
+    fs::write(directory.join("input.txt"), "")?;
+
+    for (path, content) in registrations.into_iter().flatten() {
+        fs::write(path, content)?;
+    }
+    Ok(crate_name)
+}
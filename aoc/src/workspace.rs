@@ -0,0 +1,18 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+
+fn is_workspace(directory: &Path) -> bool {
+    fs::read_to_string(directory.join("Cargo.toml"))
+        .is_ok_and(|manifest| manifest.lines().any(|line| line.trim() == "[workspace]"))
+}
+
+/// The workspace holding the day crates: the first directory from `start`
+/// upward whose `Cargo.toml` declares a `[workspace]`, or else the one the
+/// runner was built in, so that it may be run from anywhere.
+pub fn root(start: &Path) -> PathBuf {
+    start.ancestors()
+        .find(|directory| is_workspace(directory))
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join(".."))
+}
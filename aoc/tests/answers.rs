@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use aoc::registry::{Day, DAYS};
+use aoc_common::{Answers, Stage};
+
+
+fn day_directory(day: &Day) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(day.directory)
+}
+
+fn check_day(day: &Day) -> Vec<String> {
+    let directory = day_directory(day);
+    let answers = match Answers::load(directory.join("answers.toml")) {
+        Ok(answers) => answers,
+        Err(error) => return vec![format!("{}: {error}", day.directory)],
+    };
+
+    let mut failures = Vec::new();
+    for stage in [Stage::One, Stage::Two] {
+        let inputs = answers.inputs(stage);
+        if inputs.is_empty() {
+            failures.push(format!("{} {stage}: no recorded answer", day.directory));
+        }
+        for input in inputs {
+            let path = directory.join(input);
+            let expected = answers.expected(stage, input);
+            match (day.solve)(stage, &path.to_string_lossy()) {
+                Ok(answer) if Some(answer.to_string()) == expected => {}
+                Ok(answer) => failures.push(format!(
+                    "{} {stage} {input}: got {answer}, expected {}",
+                    day.directory, expected.unwrap_or_default()
+                )),
+                Err(error) => failures.push(format!("{} {stage} {input}: {error}", day.directory)),
+            }
+        }
+    }
+    failures
+}
+
+#[test]
+fn recorded_answers() {
+    let failures: Vec<String> = DAYS.iter().flat_map(check_day).collect();
+    assert!(failures.is_empty(), "answers changed:\n{}", failures.join("\n"));
+}
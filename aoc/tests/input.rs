@@ -0,0 +1,41 @@
+use std::fs;
+use std::io::Cursor;
+
+use aoc_common::{Solver, Stage};
+
+
+#[test]
+fn inline_input_matches_file() {
+    let test = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../day05/test.txt"))
+        .expect("Unable to read day05 test input");
+    for stage in [Stage::One, Stage::Two] {
+        let from_str = day05::Puzzle.solve_str(stage, &test).expect("Unable to solve from str");
+        let from_reader = day05::Puzzle.solve_reader(stage, Cursor::new(&test)).expect("Unable to solve from reader");
+        assert_eq!(from_str, from_reader);
+    }
+}
+
+#[test]
+fn inline_input() {
+    let answer = day02::Puzzle.solve_str(Stage::One, "A Y\nB X\nC Z").expect("Unable to solve");
+    assert_eq!(answer.to_string(), "15");
+}
+
+#[test]
+fn streamed_calories_match_stages() {
+    let input = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../day01/input.txt"))
+        .expect("Unable to read day01 input");
+    for stage in [Stage::One, Stage::Two] {
+        let tally = day01::CalorieTally::from_reader(Cursor::new(&input), day01::elves_count_for(stage))
+            .expect("Unable to stream the input");
+        let answer = day01::Puzzle.solve_str(stage, &input).expect("Unable to solve");
+        assert_eq!(tally.max_calories().map(aoc_common::Answer::from), Some(answer));
+    }
+}
+
+#[test]
+fn reader_errors_stop_the_solver() {
+    let input: &[u8] = b"A Y\nB \xff\nC Z";
+    let error = day02::Puzzle.solve_reader(Stage::One, Cursor::new(input)).expect_err("Invalid UTF-8 was solved");
+    assert!(matches!(error, aoc_common::Error::Io(_)), "expected an I/O error, got {error:?}");
+}
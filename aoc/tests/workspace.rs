@@ -0,0 +1,14 @@
+use std::path::Path;
+
+use aoc::workspace;
+
+
+#[test]
+fn workspace_root_is_found_from_anywhere() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let expected = root.canonicalize().expect("Unable to resolve the workspace");
+    for start in [root.join("day01/src"), root.clone(), std::env::temp_dir()] {
+        let found = workspace::root(&start).canonicalize().expect("Unable to resolve the root");
+        assert_eq!(found, expected);
+    }
+}
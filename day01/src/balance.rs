@@ -0,0 +1,248 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+use crate::ElfGroup;
+
+
+/// Above this number of items, [`BalanceMethod::Auto`] only runs the greedy heuristic.
+pub const EXACT_MAX_ITEMS: usize = 24;
+
+/// Search nodes after which the exact solver gives up and keeps its best assignment.
+const EXACT_NODES_BUDGET: usize = 20_000_000;
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BalanceMethod {
+    Auto,
+    Exact,
+    Greedy,
+}
+
+#[derive(Debug)]
+pub enum BalanceError {
+    NoElves,
+    TooManyItems { items: usize, capacity: usize },
+}
+
+impl fmt::Display for BalanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoElves => write!(f, "No elves to carry the food"),
+            Self::TooManyItems { items, capacity } =>
+                write!(f, "{items} food items do not fit in the {capacity} slots allowed by the cap"),
+        }
+    }
+}
+
+impl std::error::Error for BalanceError {}
+
+
+/// Food items reassigned to the elves, with the largest per-elf total it leads to.
+pub struct Rebalance {
+    pub assignment: Vec<Vec<u32>>,
+    pub max_total: u64,
+    /// No assignment can do better than this.
+    pub lower_bound: u64,
+    /// Whether `max_total` is proven optimal.
+    pub optimal: bool,
+}
+
+impl fmt::Display for Rebalance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, food) in self.assignment.iter().enumerate() {
+            let items: Vec<String> = food.iter().map(u32::to_string).collect();
+            let total: u64 = food.iter().map(|&item| u64::from(item)).sum();
+            writeln!(f, "Elf {}: {total} = {}", index + 1, items.join(" + "))?;
+        }
+        let quality = if self.optimal { "optimal" } else { "heuristic" };
+        write!(f, "Largest load {} ({quality}), lower bound {}", self.max_total, self.lower_bound)
+    }
+}
+
+
+struct Loads {
+    totals: Vec<u64>,
+    counts: Vec<usize>,
+}
+
+/// Longest processing time first: the biggest remaining item goes to the least
+/// loaded elf that still has room for it.
+fn greedy(items: &[u32], elves_count: usize, max_items: usize) -> Vec<Vec<u32>> {
+    let mut assignment = vec![Vec::new(); elves_count];
+    let mut heap: BinaryHeap<Reverse<(u64, usize)>> = (0..elves_count).map(|index| Reverse((0, index))).collect();
+    for &item in items {
+        let Reverse((load, index)) = heap.pop().expect("Capacity checked beforehand");
+        assignment[index].push(item);
+        if assignment[index].len() < max_items {
+            heap.push(Reverse((load + u64::from(item), index)));
+        }
+    }
+    assignment
+}
+
+/// Depth-first branch and bound over the items sorted in decreasing order.
+struct ExactSearch<'a> {
+    items: &'a [u32],
+    max_items: usize,
+    lower_bound: u64,
+    best_total: u64,
+    best: Vec<usize>,
+    current: Vec<usize>,
+    nodes: usize,
+}
+
+impl ExactSearch<'_> {
+
+    /// Returns false once the search can stop, either because the bound is
+    /// reached or because the budget is spent.
+    fn search(&mut self, item_index: usize, loads: &mut Loads) -> bool {
+        self.nodes += 1;
+        if self.nodes > EXACT_NODES_BUDGET {
+            return false;
+        }
+        let Some(&item) = self.items.get(item_index) else {
+            let max_total = loads.totals.iter().copied().max().unwrap_or(0);
+            if max_total < self.best_total {
+                self.best_total = max_total;
+                self.best = self.current.clone();
+            }
+            return self.best_total > self.lower_bound;
+        };
+        let item = u64::from(item);
+
+        let mut tried: Vec<(u64, usize)> = Vec::new();
+        for elf in 0..loads.totals.len() {
+            let state = (loads.totals[elf], loads.counts[elf]);
+            // Elves in the same state lead to the same assignments
+            if loads.counts[elf] >= self.max_items || state.0 + item >= self.best_total || tried.contains(&state) {
+                continue;
+            }
+            tried.push(state);
+            loads.totals[elf] += item;
+            loads.counts[elf] += 1;
+            self.current.push(elf);
+            let carry_on = self.search(item_index + 1, loads);
+            self.current.pop();
+            loads.totals[elf] -= item;
+            loads.counts[elf] -= 1;
+            if !carry_on {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+
+impl ElfGroup {
+
+    /// Reassigns every food item to minimise the largest per-elf total, with at
+    /// most `max_items` items per elf. This is multiway number partitioning: the
+    /// exact solver is exponential, the greedy one is within 4/3 of the optimum
+    /// when there is no cap.
+    pub fn rebalance(&self, max_items: Option<usize>, method: BalanceMethod) -> Result<Rebalance, BalanceError> {
+        let elves_count = self.elves.len();
+        if elves_count == 0 {
+            return Err(BalanceError::NoElves);
+        }
+        let mut items: Vec<u32> = self.elves.iter().flat_map(|elf| elf.food().iter().copied()).collect();
+        items.sort_unstable_by_key(|&item| Reverse(item));
+        let max_items = max_items.unwrap_or(items.len().max(1));
+        let capacity = max_items.saturating_mul(elves_count);
+        if items.len() > capacity {
+            return Err(BalanceError::TooManyItems { items: items.len(), capacity });
+        }
+
+        let total: u64 = items.iter().map(|&item| u64::from(item)).sum();
+        let lower_bound = total.div_ceil(elves_count as u64).max(items.first().copied().unwrap_or(0).into());
+
+        let greedy_assignment = greedy(&items, elves_count, max_items);
+        let greedy_total = greedy_assignment.iter()
+            .map(|food| food.iter().map(|&item| u64::from(item)).sum())
+            .max()
+            .unwrap_or(0);
+        let run_exact = match method {
+            BalanceMethod::Auto => items.len() <= EXACT_MAX_ITEMS,
+            BalanceMethod::Exact => true,
+            BalanceMethod::Greedy => false,
+        };
+        if !run_exact || greedy_total == lower_bound {
+            return Ok(Rebalance {
+                assignment: greedy_assignment,
+                max_total: greedy_total,
+                lower_bound,
+                optimal: greedy_total == lower_bound,
+            });
+        }
+
+        let mut search = ExactSearch {
+            items: &items,
+            max_items,
+            lower_bound,
+            best_total: greedy_total,
+            best: Vec::new(),
+            current: Vec::with_capacity(items.len()),
+            nodes: 0,
+        };
+        let mut loads = Loads { totals: vec![0; elves_count], counts: vec![0; elves_count] };
+        let completed = search.search(0, &mut loads);
+        let optimal = completed || search.best_total == lower_bound;
+        if search.best.is_empty() {
+            return Ok(Rebalance { assignment: greedy_assignment, max_total: greedy_total, lower_bound, optimal });
+        }
+
+        let mut assignment = vec![Vec::new(); elves_count];
+        for (&item, &elf) in items.iter().zip(search.best.iter()) {
+            assignment[elf].push(item);
+        }
+        Ok(Rebalance { assignment, max_total: search.best_total, lower_bound, optimal })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::lines_from_str;
+
+    use super::*;
+
+    fn loads(rebalance: &Rebalance) -> Vec<u64> {
+        rebalance.assignment.iter().map(|food| food.iter().map(|&item| u64::from(item)).sum()).collect()
+    }
+
+    #[test]
+    fn exact_solver_beats_lpt() {
+        let elf_group = ElfGroup::parse(lines_from_str("3\n3\n2\n\n2\n2")).unwrap();
+
+        let greedy = elf_group.rebalance(None, BalanceMethod::Greedy).unwrap();
+        assert_eq!((greedy.max_total, greedy.lower_bound, greedy.optimal), (7, 6, false));
+        assert_eq!(loads(&greedy), [7, 5]);
+
+        let exact = elf_group.rebalance(None, BalanceMethod::Exact).unwrap();
+        assert_eq!((exact.max_total, exact.lower_bound, exact.optimal), (6, 6, true));
+        assert_eq!(loads(&exact), [6, 6]);
+        assert_eq!(elf_group.rebalance(None, BalanceMethod::Auto).unwrap().max_total, 6);
+    }
+
+    #[test]
+    fn cap_limits_items_per_elf() {
+        let elf_group = ElfGroup::parse(lines_from_str("5\n1\n\n1\n1")).unwrap();
+        assert_eq!(elf_group.rebalance(None, BalanceMethod::Exact).unwrap().max_total, 5);
+
+        let capped = elf_group.rebalance(Some(2), BalanceMethod::Exact).unwrap();
+        assert_eq!((capped.max_total, capped.lower_bound, capped.optimal), (6, 5, true));
+        assert!(capped.assignment.iter().all(|food| food.len() <= 2));
+        assert_eq!(elf_group.rebalance(Some(2), BalanceMethod::Greedy).unwrap().max_total, 6);
+    }
+
+    #[test]
+    fn rejects_more_items_than_slots() {
+        let elf_group = ElfGroup::parse(lines_from_str("1\n2\n3")).unwrap();
+        assert!(matches!(
+            elf_group.rebalance(Some(2), BalanceMethod::Auto),
+            Err(BalanceError::TooManyItems { items: 3, capacity: 2 })
+        ));
+        assert!(matches!(ElfGroup::new().rebalance(None, BalanceMethod::Auto), Err(BalanceError::NoElves)));
+    }
+}
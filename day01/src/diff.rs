@@ -0,0 +1,195 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+use serde_json::{json, Value};
+
+use crate::{Elf, ElfGroup};
+
+
+/// What happened to one elf between two snapshots. A `None` total means the
+/// elf is missing from that snapshot.
+pub struct ElfChange {
+    pub elf: String,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+    pub added: Vec<u32>,
+    pub removed: Vec<u32>,
+}
+
+/// 1-based rank in the top-N of each snapshot, `None` when outside of it.
+pub struct RankChange {
+    pub elf: String,
+    pub before: Option<usize>,
+    pub after: Option<usize>,
+}
+
+pub struct InventoryDiff {
+    pub top: usize,
+    pub changes: Vec<ElfChange>,
+    pub ranks: Vec<RankChange>,
+}
+
+/// Elves are matched by id when the inventory has some, by position otherwise.
+fn elf_key(index: usize, elf: &Elf) -> String {
+    elf.id().map_or_else(|| format!("#{}", index + 1), String::from)
+}
+
+/// Items of `lhs` missing from `rhs`, counting duplicates.
+fn missing_items(lhs: &[u32], rhs: &[u32]) -> Vec<u32> {
+    let mut lhs = lhs.to_vec();
+    let mut rhs = rhs.to_vec();
+    lhs.sort_unstable();
+    rhs.sort_unstable();
+
+    let mut missing = Vec::new();
+    let mut others = rhs.iter().peekable();
+    for item in lhs {
+        while others.next_if(|other| **other < item).is_some() {}
+        if others.next_if_eq(&&item).is_none() {
+            missing.push(item);
+        }
+    }
+    missing
+}
+
+impl ElfGroup {
+
+    fn keyed(&self) -> Vec<(String, &Elf)> {
+        self.elves.iter().enumerate().map(|(index, elf)| (elf_key(index, elf), elf)).collect()
+    }
+
+    fn ranks(&self, top: usize) -> Vec<String> {
+        self.top_n(top).iter().map(|ranked| elf_key(ranked.index, ranked.elf)).collect()
+    }
+
+    /// Compares this snapshot with a `newer` one, elf by elf, and the `top`
+    /// most loaded elves of both. The loaders merge the elves sharing an id,
+    /// so that every key stands for a single elf.
+    pub fn diff(&self, newer: &ElfGroup, top: usize) -> InventoryDiff {
+        let before = self.keyed();
+        let after = newer.keyed();
+        let old_elves: HashMap<&str, &Elf> = before.iter().map(|(key, elf)| (key.as_str(), *elf)).collect();
+        let new_elves: HashMap<&str, &Elf> = after.iter().map(|(key, elf)| (key.as_str(), *elf)).collect();
+        let mut keys: Vec<&String> = before.iter().map(|(key, _)| key).collect();
+        keys.extend(after.iter().map(|(key, _)| key).filter(|key| !old_elves.contains_key(key.as_str())));
+
+        let mut changes = Vec::new();
+        for key in keys {
+            let old_food = old_elves.get(key.as_str()).map(|elf| elf.food());
+            let new_food = new_elves.get(key.as_str()).map(|elf| elf.food());
+            let added = missing_items(new_food.unwrap_or_default(), old_food.unwrap_or_default());
+            let removed = missing_items(old_food.unwrap_or_default(), new_food.unwrap_or_default());
+            let is_same_elf = old_food.is_some() && new_food.is_some();
+            if is_same_elf && added.is_empty() && removed.is_empty() {
+                continue;
+            }
+            changes.push(ElfChange {
+                elf: key.clone(),
+                before: old_food.map(|food| food.iter().map(|&item| u64::from(item)).sum()),
+                after: new_food.map(|food| food.iter().map(|&item| u64::from(item)).sum()),
+                added,
+                removed,
+            });
+        }
+
+        let old_ranks = self.ranks(top);
+        let new_ranks = newer.ranks(top);
+        let rank_by_key = |ranks: &[String]| -> HashMap<String, usize> {
+            ranks.iter().enumerate().map(|(rank, key)| (key.clone(), rank + 1)).collect()
+        };
+        let (old_rank_of, new_rank_of) = (rank_by_key(&old_ranks), rank_by_key(&new_ranks));
+        let mut ranks: Vec<RankChange> = old_ranks.iter()
+            .chain(new_ranks.iter().filter(|key| !old_rank_of.contains_key(*key)))
+            .map(|key| RankChange {
+                elf: key.clone(),
+                before: old_rank_of.get(key).copied(),
+                after: new_rank_of.get(key).copied(),
+            })
+            .filter(|change| change.before != change.after)
+            .collect();
+        ranks.sort_by_key(|change| (change.after.is_none(), change.after, change.before));
+
+        InventoryDiff { top, changes, ranks }
+    }
+}
+
+impl ElfChange {
+
+    pub fn delta(&self) -> i64 {
+        self.after.unwrap_or(0) as i64 - self.before.unwrap_or(0) as i64
+    }
+}
+
+impl InventoryDiff {
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.ranks.is_empty()
+    }
+
+    pub fn to_json(&self) -> Value {
+        let changes: Vec<Value> = self.changes.iter()
+            .map(|change| json!({
+                "elf": change.elf,
+                "before": change.before,
+                "after": change.after,
+                "delta": change.delta(),
+                "added": change.added,
+                "removed": change.removed,
+            }))
+            .collect();
+        let ranks: Vec<Value> = self.ranks.iter()
+            .map(|change| json!({ "elf": change.elf, "before": change.before, "after": change.after }))
+            .collect();
+        json!({ "top": self.top, "changes": changes, "ranks": ranks })
+    }
+}
+
+fn items(items: &[u32]) -> String {
+    items.iter().map(u32::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn rank(rank: Option<usize>) -> String {
+    rank.map_or_else(|| String::from("-"), |rank| format!("#{rank}"))
+}
+
+impl fmt::Display for InventoryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+        for change in &self.changes {
+            match (change.before, change.after) {
+                (None, Some(after)) => write!(f, "+ elf {} carries {after} calories", change.elf)?,
+                (Some(before), None) => write!(f, "- elf {} carried {before} calories", change.elf)?,
+                (before, after) => {
+                    let (before, after) = (before.unwrap_or(0), after.unwrap_or(0));
+                    write!(f, "~ elf {} {before} -> {after} calories ({:+})", change.elf, change.delta())?;
+                    if !change.added.is_empty() {
+                        write!(f, ", added {}", items(&change.added))?;
+                    }
+                    if !change.removed.is_empty() {
+                        write!(f, ", removed {}", items(&change.removed))?;
+                    }
+                }
+            }
+            writeln!(f)?;
+        }
+
+        if !self.ranks.is_empty() {
+            writeln!(f, "Top {} ranking:", self.top)?;
+        }
+        for change in &self.ranks {
+            let trend = match (change.before, change.after) {
+                (None, _) => "enters",
+                (_, None) => "leaves",
+                (Some(before), Some(after)) => match after.cmp(&before) {
+                    Ordering::Less => "up",
+                    _ => "down",
+                },
+            };
+            writeln!(f, "  elf {} {} -> {} ({trend})", change.elf, rank(change.before), rank(change.after))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+use std::str::FromStr;
+
+use aoc_common::{in_file, read_lines, Diagnostic, Error, Reason};
+use serde_json::Value;
+
+use crate::{Elf, ElfGroup};
+
+
+/// The layouts an inventory may come in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputFormat {
+    /// One calories count per line, elves separated by blank lines.
+    Blank,
+    /// `elf_id,calories` rows, with an optional header.
+    Csv,
+    /// An array of elves, each an array of calories or `{"id": ..., "calories": [...]}`.
+    Json,
+}
+
+impl FromStr for InputFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blank" => Ok(Self::Blank),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(Error::InvalidArgument(String::from(s))),
+        }
+    }
+}
+
+impl InputFormat {
+
+    pub fn from_path(path: impl AsRef<Path>) -> Option<InputFormat> {
+        match path.as_ref().extension()?.to_str()? {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "txt" => Some(Self::Blank),
+            _ => None,
+        }
+    }
+
+    /// Guesses the format from the first line which is not blank, for inputs
+    /// read without a file name such as the standard input.
+    pub fn sniff(lines: &[String]) -> InputFormat {
+        let first = lines.iter().map(|line| line.trim()).find(|line| !line.is_empty());
+        match first {
+            Some(line) if line.starts_with('[') => Self::Json,
+            Some(line) if line.contains(',') => Self::Csv,
+            _ => Self::Blank,
+        }
+    }
+}
+
+
+#[derive(Debug)]
+pub enum FormatError {
+    MissingCalories { column: usize },
+    InvalidCalories { span: Range<usize> },
+    InvalidJson { message: String, column: usize },
+    InvalidElf { index: usize, column: usize },
+    NotAnArray { column: usize },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingCalories { .. } => write!(f, "Expecting an 'elf_id,calories' row"),
+            Self::InvalidCalories { .. } => write!(f, "Invalid calories, expecting a number"),
+            Self::InvalidJson { message, .. } => write!(f, "Invalid JSON: {message}"),
+            Self::InvalidElf { index, .. } => write!(
+                f, "Elf #{} should be an array of calories or an object with an id and calories", index + 1
+            ),
+            Self::NotAnArray { .. } => write!(f, "Expecting an array of elves"),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl Reason for FormatError {
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Self::MissingCalories { column } => Some(*column..*column + 1),
+            Self::InvalidCalories { span } => Some(span.clone()),
+            Self::InvalidJson { column, .. } | Self::InvalidElf { column, .. } | Self::NotAnArray { column } => {
+                Some(*column..*column + 1)
+            }
+        }
+    }
+}
+
+
+/// Reads an inventory in any of the [`InputFormat`]s.
+#[derive(Clone, Copy, Default)]
+pub struct Loader {
+    /// Guessed from the file extension, then from the content when `None`.
+    pub format: Option<InputFormat>,
+    /// Lets any line which is not a number separate elves in the blank-line format.
+    pub lenient: bool,
+}
+
+impl Loader {
+
+    pub fn load(&self, lines: impl Iterator<Item = String>) -> Result<ElfGroup, Error> {
+        let lines: Vec<String> = lines.collect();
+        let format = self.format.unwrap_or_else(|| InputFormat::sniff(&lines));
+        self.load_as(format, lines)
+    }
+
+    pub fn load_file(&self, path: &str) -> Result<ElfGroup, Error> {
+        let lines = read_lines(path)?.collect::<Result<Vec<String>, Error>>()?;
+        let format = self.format
+            .or_else(|| InputFormat::from_path(path))
+            .unwrap_or_else(|| InputFormat::sniff(&lines));
+        self.load_as(format, lines).map_err(|error| in_file(error, path))
+    }
+
+    fn load_as(&self, format: InputFormat, lines: Vec<String>) -> Result<ElfGroup, Error> {
+        match format {
+            InputFormat::Blank if self.lenient => {
+                let mut elf_group = ElfGroup::new();
+                elf_group.load(lines.into_iter());
+                Ok(elf_group)
+            }
+            InputFormat::Blank => Ok(ElfGroup::parse(lines.into_iter())?),
+            InputFormat::Csv => Ok(ElfGroup::from_csv(lines.into_iter())?),
+            InputFormat::Json => ElfGroup::from_json(&lines.join("\n")),
+        }
+    }
+}
+
+
+impl ElfGroup {
+
+    /// Groups `elf_id,calories` rows by elf, in order of first appearance. A
+    /// first non-blank row whose calories are not a number is taken as a header.
+    pub fn from_csv(lines: impl Iterator<Item = String>) -> Result<ElfGroup, Vec<Diagnostic>> {
+        let mut elf_group = ElfGroup::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let mut diagnostics = Vec::new();
+        let mut first_row = true;
+        for (index, line) in lines.enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let is_header_row = std::mem::replace(&mut first_row, false);
+            let Some((id, calories)) = line.split_once(',') else {
+                let error = FormatError::MissingCalories { column: line.len() };
+                diagnostics.push(Diagnostic::new(index + 1, &line, error));
+                continue;
+            };
+            let calories = match calories.trim().parse() {
+                Ok(calories) => calories,
+                Err(_error) if is_header_row => continue,
+                Err(_error) => {
+                    let start = id.len() + 1;
+                    let error = FormatError::InvalidCalories { span: start..line.len() };
+                    diagnostics.push(Diagnostic::new(index + 1, &line, error));
+                    continue;
+                }
+            };
+            let id = id.trim();
+            let position = *positions.entry(String::from(id)).or_insert_with(|| {
+                elf_group.elves.push(Elf::with_id(id));
+                elf_group.elves.len() - 1
+            });
+            elf_group.elves[position].add_calories(calories);
+        }
+
+        if diagnostics.is_empty() {
+            Ok(elf_group)
+        } else {
+            Err(diagnostics)
+        }
+    }
+
+    /// Reads an array of elves, each either an array of calories or an object
+    /// with an `id` and its `calories`. Like CSV rows, the elves sharing an id
+    /// are merged into the first one.
+    pub fn from_json(content: &str) -> Result<ElfGroup, Error> {
+        let value: Value = serde_json::from_str(content).map_err(|error| {
+            let text = content.lines().nth(error.line().saturating_sub(1)).unwrap_or_default();
+            let message = error.to_string();
+            let message = message.split(" at line").next().unwrap_or_default().to_string();
+            let column = error.column().saturating_sub(1);
+            Diagnostic::new(error.line(), text, FormatError::InvalidJson { message, column })
+        })?;
+        let element_starts = element_starts(content);
+        let invalid_elf = |index: usize| -> Error {
+            let start = element_starts.get(index).copied().unwrap_or_default();
+            let line_start = content[..start].rfind('\n').map_or(0, |newline| newline + 1);
+            let line = content[..start].matches('\n').count() + 1;
+            let text = content[line_start..].lines().next().unwrap_or_default();
+            Diagnostic::new(line, text, FormatError::InvalidElf { index, column: start - line_start }).into()
+        };
+
+        let elves = value.as_array().ok_or_else(|| {
+            let (index, text) = content.lines().enumerate().find(|(_, line)| !line.trim().is_empty()).unwrap_or_default();
+            let column = text.len() - text.trim_start().len();
+            Diagnostic::new(index + 1, text, FormatError::NotAnArray { column })
+        })?;
+        let mut elf_group = ElfGroup::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for (index, elf) in elves.iter().enumerate() {
+            let (id, calories) = match elf {
+                Value::Array(calories) => (None, calories),
+                Value::Object(fields) => {
+                    let id = match fields.get("id") {
+                        Some(Value::String(id)) => Some(id.clone()),
+                        Some(Value::Number(id)) => Some(id.to_string()),
+                        None => None,
+                        Some(_) => return Err(invalid_elf(index)),
+                    };
+                    let calories = fields.get("calories").and_then(Value::as_array).ok_or_else(|| invalid_elf(index))?;
+                    (id, calories)
+                }
+                _ => return Err(invalid_elf(index)),
+            };
+            let position = match &id {
+                Some(id) => *positions.entry(id.clone()).or_insert(elf_group.elves.len()),
+                None => elf_group.elves.len(),
+            };
+            if position == elf_group.elves.len() {
+                elf_group.elves.push(Elf { id, food: Vec::new() });
+            }
+            for calories in calories {
+                let calories = calories.as_u64()
+                    .and_then(|calories| u32::try_from(calories).ok())
+                    .ok_or_else(|| invalid_elf(index))?;
+                elf_group.elves[position].add_calories(calories);
+            }
+        }
+        Ok(elf_group)
+    }
+}
+
+/// Byte offsets of the elements of the top-level array in `content`, which
+/// must be valid JSON: `serde_json::Value` does not keep track of positions.
+fn element_starts(content: &str) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut expecting_element = false;
+    for (offset, c) in content.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        if expecting_element && !c.is_whitespace() && c != ']' {
+            starts.push(offset);
+            expecting_element = false;
+        }
+        match c {
+            '"' => in_string = true,
+            '[' | '{' => {
+                depth += 1;
+                expecting_element = depth == 1;
+            }
+            ']' | '}' => depth -= 1,
+            ',' if depth == 1 => expecting_element = true,
+            _ => {}
+        }
+    }
+    starts
+}
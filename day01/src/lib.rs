@@ -0,0 +1,291 @@
+mod balance;
+mod diff;
+mod format;
+mod stats;
+mod stream;
+mod target;
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::ops::Range;
+
+use aoc_common::{Answer, Diagnostic, Error, Reason, Solver, Stage};
+
+pub use balance::{BalanceError, BalanceMethod, Rebalance, EXACT_MAX_ITEMS};
+pub use diff::{ElfChange, InventoryDiff, RankChange};
+pub use format::{FormatError, InputFormat, Loader};
+pub use stats::{CalorieStats, ElfTotal, Histogram, HistogramBin};
+pub use stream::CalorieTally;
+
+
+#[derive(Debug)]
+pub enum ParseError {
+    InvalidCalories { span: Range<usize> },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCalories { .. } => write!(f, "Expecting calories or a blank line between elves"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Reason for ParseError {
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Self::InvalidCalories { span } => Some(span.clone()),
+        }
+    }
+}
+
+
+#[derive(Default)]
+pub struct Elf {
+    id: Option<String>,
+    food: Vec<u32>,
+}
+
+impl Elf {
+
+    pub fn new() -> Elf {
+        Elf {
+            id: None,
+            food: Vec::new()
+        }
+    }
+
+    pub fn with_id(id: &str) -> Elf {
+        Elf {
+            id: Some(String::from(id)),
+            food: Vec::new()
+        }
+    }
+
+    /// The identifier given by CSV and JSON inventories.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+
+    pub fn add_calories(&mut self, calories: u32) {
+        self.food.push(calories);
+    }
+
+    /// Widened so that many large items cannot overflow.
+    pub fn total_calories(&self) -> u64 {
+        self.food.iter().map(|&calories| u64::from(calories)).sum()
+    }
+
+    pub fn food(&self) -> &[u32] {
+        &self.food
+    }
+
+    pub fn items_count(&self) -> usize {
+        self.food.len()
+    }
+}
+
+/// An elf selected by [`ElfGroup::top_n`], with its position in the input.
+pub struct RankedElf<'a> {
+    pub index: usize,
+    pub elf: &'a Elf,
+    pub total: u64,
+}
+
+#[derive(Default)]
+pub struct ElfGroup {
+    elves: Vec<Elf>,
+}
+
+impl ElfGroup<> {
+
+    pub fn new() -> ElfGroup {
+        ElfGroup {
+            elves: Vec::new()
+        }
+    }
+
+    /// Lenient loading: any line which is not a number closes the current elf.
+    pub fn load(&mut self, it: impl Iterator<Item = String>) {
+        let mut elf = Elf::new();
+        for line in it {
+            match line.parse() {
+                Ok(calories) => {
+                    elf.add_calories(calories);
+                }
+                Err(_error) => {
+                    self.elves.push(elf);
+                    elf = Elf::new();
+                }
+            }
+        }
+        if elf.items_count() > 0 {
+            self.elves.push(elf);
+        }
+    }
+
+    /// Strict loading: only blank lines separate elves, and every other line
+    /// which is not a number is reported.
+    pub fn parse(lines: impl Iterator<Item = String>) -> Result<ElfGroup, Vec<Diagnostic>> {
+        let mut elf_group = ElfGroup::new();
+        let mut diagnostics = Vec::new();
+        let mut elf = Elf::new();
+        for (index, line) in lines.enumerate() {
+            let calories = line.trim();
+            if calories.is_empty() {
+                if elf.items_count() > 0 {
+                    elf_group.elves.push(std::mem::take(&mut elf));
+                }
+                continue;
+            }
+            match calories.parse() {
+                Ok(calories) => elf.add_calories(calories),
+                Err(_error) => {
+                    let start = line.len() - line.trim_start().len();
+                    let span = start..start + calories.len();
+                    diagnostics.push(Diagnostic::new(index + 1, &line, ParseError::InvalidCalories { span }));
+                }
+            }
+        }
+        if elf.items_count() > 0 {
+            elf_group.elves.push(elf);
+        }
+
+        if diagnostics.is_empty() {
+            Ok(elf_group)
+        } else {
+            Err(diagnostics)
+        }
+    }
+
+    pub fn elves_count(&self) -> usize {
+        self.elves.len()
+    }
+
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    /// The `k` elves carrying the most calories, most loaded first. Ties are
+    /// broken by input order. Keeps a min-heap of `k` elves: O(n log k).
+    pub fn top_n(&self, k: usize) -> Vec<RankedElf<'_>> {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for (index, elf) in self.elves.iter().enumerate() {
+            heap.push(Reverse((elf.total_calories(), Reverse(index))));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(index)))| RankedElf { index, elf: &self.elves[index], total })
+            .collect()
+    }
+
+    pub fn get_nth_max_calories(&self, count: usize) -> Option<u64> {
+        if self.elves.is_empty() {
+            return None;
+        }
+        let total = self.top_n(count)
+            .iter()
+            .map(|ranked| ranked.total)
+            .sum();
+        Some(total)
+    }
+}
+
+/// Number of elves whose calories are summed for each stage.
+pub fn elves_count_for(stage: Stage) -> usize {
+    match stage {
+        Stage::One => 1,
+        Stage::Two => 3,
+    }
+}
+
+fn elves_calories(elf_group: &ElfGroup, count: usize) -> Result<Answer, Error> {
+    let max_calories = elf_group.get_nth_max_calories(count).ok_or("No elves in the list")?;
+    Ok(max_calories.into())
+}
+
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    type Input = ElfGroup;
+
+    fn parse(&self, _stage: Stage, lines: impl Iterator<Item = String>) -> Result<ElfGroup, Error> {
+        Loader::default().load(lines)
+    }
+
+    fn part1(&self, elf_group: ElfGroup) -> Result<Answer, Error> {
+        elves_calories(&elf_group, elves_count_for(Stage::One))
+    }
+
+    fn part2(&self, elf_group: ElfGroup) -> Result<Answer, Error> {
+        elves_calories(&elf_group, elves_count_for(Stage::Two))
+    }
+}
+
+/// [`Puzzle`] reading a given format, or leniently.
+impl Solver for Loader {
+    type Input = ElfGroup;
+
+    fn parse(&self, _stage: Stage, lines: impl Iterator<Item = String>) -> Result<ElfGroup, Error> {
+        self.load(lines)
+    }
+
+    fn part1(&self, elf_group: ElfGroup) -> Result<Answer, Error> {
+        Puzzle.part1(elf_group)
+    }
+
+    fn part2(&self, elf_group: ElfGroup) -> Result<Answer, Error> {
+        Puzzle.part2(elf_group)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::lines_from_str;
+
+    use super::*;
+
+    fn top(input: &str, k: usize) -> Vec<(usize, u64)> {
+        let elf_group = ElfGroup::parse(lines_from_str(input)).unwrap();
+        elf_group.top_n(k).iter().map(|ranked| (ranked.index, ranked.total)).collect()
+    }
+
+    #[test]
+    fn top_n_counts_the_final_elf() {
+        assert_eq!(top("1000\n2000\n\n4000\n\n5000", 2), [(2, 5000), (1, 4000)]);
+        assert_eq!(top("1000\n\n9000", 1), [(1, 9000)]);
+    }
+
+    #[test]
+    fn top_n_keeps_input_indices() {
+        assert_eq!(top("10\n\n30\n\n20\n\n40", 3), [(3, 40), (1, 30), (2, 20)]);
+    }
+
+    #[test]
+    fn top_n_ranks_earlier_elves_first_on_ties() {
+        assert_eq!(top("5\n\n7\n\n3\n\n7", 3), [(1, 7), (3, 7), (0, 5)]);
+        assert_eq!(top("3\n\n7\n\n3\n4", 1), [(1, 7)]);
+    }
+
+    #[test]
+    fn top_n_bounds() {
+        assert!(top("1\n\n2", 0).is_empty());
+        assert_eq!(top("1\n\n2", 5), [(1, 2), (0, 1)]);
+        assert!(top("", 3).is_empty());
+    }
+
+    #[test]
+    fn totals_do_not_overflow() {
+        let elf_group = ElfGroup::parse(lines_from_str("4000000000\n4000000000\n\n3000000000")).unwrap();
+        assert_eq!(elf_group.get_nth_max_calories(1), Some(8_000_000_000));
+        assert_eq!(elf_group.get_nth_max_calories(3), Some(11_000_000_000));
+    }
+}
@@ -1,109 +1,192 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use aoc_common::{run_day_with, Args, Error, Format, Stage};
 
-pub struct Elf {
-    food: Vec<u32>,
+use day01::{elves_count_for, BalanceMethod, ElfTotal, InputFormat, Loader};
+
+
+fn sentence(stage: Stage, max_calories: impl std::fmt::Display) -> String {
+    let who = match stage {
+        Stage::One => "elf",
+        Stage::Two => "3 elves",
+    };
+    format!("The {who} carrying the most calories has {max_calories} calories")
 }
 
-impl Elf {
+fn top(loader: Loader, mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let count = args.next().ok_or(Error::MissingArgument("a number of elves"))?;
+    let count = count.parse().map_err(|_| Error::InvalidArgument(count))?;
+    let path = args.next().ok_or(Error::MissingArgument("a file name"))?;
 
-    pub fn new() -> Elf {
-        Elf {
-            food: Vec::new()
-        }
+    let elf_group = loader.load_file(&path)?;
+    for (rank, ranked) in elf_group.top_n(count).iter().enumerate() {
+        println!("#{} elf {} carries {} calories", rank + 1, ranked.index + 1, ranked.total);
     }
+    Ok(())
+}
 
-    pub fn add_calories(&mut self, calories: u32) {
-        self.food.push(calories);
+fn balance(loader: Loader, mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut path = None;
+    let mut max_items = None;
+    let mut method = BalanceMethod::Auto;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-items" => {
+                let value = args.next().ok_or(Error::MissingArgument("a number of items"))?;
+                max_items = Some(value.parse().map_err(|_| Error::InvalidArgument(value))?);
+            }
+            "--method" => {
+                method = match args.next().ok_or(Error::MissingArgument("a balancing method"))?.as_str() {
+                    "auto" => BalanceMethod::Auto,
+                    "exact" => BalanceMethod::Exact,
+                    "greedy" => BalanceMethod::Greedy,
+                    other => return Err(Error::InvalidArgument(String::from(other))),
+                };
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(Error::InvalidArgument(arg)),
+        }
     }
+    let path = path.ok_or(Error::MissingArgument("a file name"))?;
 
-    pub fn total_calories(&self) -> u32 {
-        self.food.iter().sum()
-    }
+    let elf_group = loader.load_file(&path)?;
+    let rebalance = elf_group.rebalance(max_items, method)
+        .map_err(|error| Error::Solver(Box::new(error)))?;
+    println!("{rebalance}");
+    Ok(())
 }
 
-pub struct ElfGroup {
-    elves: Vec<Elf>,
+fn stats(loader: Loader, mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut path = None;
+    let mut bins_count = 10;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bins" => {
+                let value = args.next().ok_or(Error::MissingArgument("a number of bins"))?;
+                bins_count = value.parse().map_err(|_| Error::InvalidArgument(value))?;
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(Error::InvalidArgument(arg)),
+        }
+    }
+    let path = path.ok_or(Error::MissingArgument("a file name"))?;
+
+    let elf_group = loader.load_file(&path)?;
+    let stats = elf_group.stats().ok_or("No elves in the list")?;
+    println!("{stats}");
+    println!();
+    println!("{}", elf_group.histogram(bins_count));
+    Ok(())
 }
 
-impl ElfGroup<> {
+fn stream(loader: Loader, mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let stage: Stage = args.next().ok_or(Error::MissingArgument("puzzle stage"))?.parse()?;
+    let path = args.next().ok_or(Error::MissingArgument("a file name"))?;
 
-    pub fn new() -> ElfGroup {
-        ElfGroup {
-            elves: Vec::new()
-        }
-    }
+    let tally = loader.tally(&path, elves_count_for(stage))?;
+    let max_calories = tally.max_calories().ok_or("No elves in the list")?;
+    println!("{}", sentence(stage, max_calories));
+    Ok(())
+}
 
-    pub fn load(&mut self, it: impl Iterator<Item = String>) {
-        let mut elf = Elf::new();
-        for line in it {
-            match line.parse() {
-                Ok(calories) => {
-                    elf.add_calories(calories);
-                }
-                Err(_error) => {
-                    self.elves.push(elf);
-                    elf = Elf::new();
-                }
-            }
+fn target(loader: Loader, mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut target = None;
+    let mut path = None;
+    let mut exact = false;
+    for arg in args.by_ref() {
+        match arg.as_str() {
+            "--exact" => exact = true,
+            _ if target.is_none() => target = Some(arg.parse().map_err(|_| Error::InvalidArgument(arg))?),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(Error::InvalidArgument(arg)),
         }
-
-        self.elves.sort_by(|x, y|
-            x.total_calories().cmp(&y.total_calories())
-        )
     }
+    let target: u64 = target.ok_or(Error::MissingArgument("a calories target"))?;
+    let path = path.ok_or(Error::MissingArgument("a file name"))?;
 
-    pub fn elves_count(&self) -> usize {
-        self.elves.len()
+    let elf_group = loader.load_file(&path)?;
+    if target > elf_group.total_calories() {
+        let message = format!("{target}, the elves only carry {} calories", elf_group.total_calories());
+        return Err(Error::InvalidArgument(message));
     }
+    let selected = if exact {
+        elf_group.subset_hitting(target).ok_or("No set of elves carries exactly this many calories")?
+    } else {
+        elf_group.smallest_set_reaching(target).ok_or("All the elves together do not reach this many calories")?
+    };
 
-    pub fn get_nth_max_calories(&self, count: usize) -> Option<u32> {
-        let Some(last_chunk) = self.elves.chunks(count).last() else { return None };
-        let total = last_chunk.iter()
-            .map(|elf| elf.total_calories())
-            .sum();
-        Some(total)
+    let total: u64 = selected.iter().map(|elf| elf.total).sum();
+    println!("{} elves carry {total} calories for a target of {target}", selected.len());
+    for ElfTotal { index, total } in selected {
+        println!("elf {} carries {total} calories", index + 1);
     }
+    Ok(())
 }
 
-fn elves_calories(path: &str, count: usize) -> Result<(), &'static str> {
-    let Ok(lines) = read_lines(path) else {
-        return Err("Unable to read the file")
-    };
-
-    let mut elf_group = ElfGroup::new();
-    elf_group.load(lines);
-
-    let Some(max_calories) = elf_group.get_nth_max_calories(count) else {
-        return Err("No elves in the list");
-    };
-    println!("The input has {} elves", elf_group.elves_count());
-    let who = if count == 1 { String::from("elf") } else { format!("{count} elves") };
-    println!("The {who} carrying the most calories has {max_calories} calories");
+fn diff(loader: Loader, mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut paths = Vec::new();
+    let mut top = 3;
+    let mut format = Format::Text;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => {
+                let value = args.next().ok_or(Error::MissingArgument("a number of elves"))?;
+                top = value.parse().map_err(|_| Error::InvalidArgument(value))?;
+            }
+            "--format" => {
+                format = args.next().ok_or(Error::MissingArgument("an output format"))?.parse()?;
+            }
+            _ if paths.len() < 2 => paths.push(arg),
+            _ => return Err(Error::InvalidArgument(arg)),
+        }
+    }
+    let [before, after] = <[String; 2]>::try_from(paths)
+        .map_err(|_| Error::MissingArgument("two inventory files"))?;
 
+    let diff = loader.load_file(&before)?.diff(&loader.load_file(&after)?, top);
+    match format {
+        Format::Text => print!("{diff}"),
+        Format::Json => println!("{}", diff.to_json()),
+    }
     Ok(())
 }
 
-fn main() -> Result<(), &'static str> {
-    let stage = std::env::args().nth(1).expect("Expecting puzzle stage");
-    let path = std::env::args().nth(2).expect("Expecting a file name");
+fn solve(loader: Loader, args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let args = Args::from_args(args)?;
+    let format = loader.format.or_else(|| InputFormat::from_path(&args.path));
+    let loader = Loader { format, ..loader };
+    run_day_with(1, &loader, args, |stage, max_calories| sentence(stage, max_calories))
+}
 
-    match stage.as_str() {
-        "stage1" => elves_calories(path.as_str(), 1),
-        "stage2" => elves_calories(path.as_str(), 3),
-        _ => Err("Unknown stage")
+/// Takes out the `--input-format` and `--lenient` flags understood by every command.
+fn loader(args: &mut Vec<String>) -> Result<Loader, Error> {
+    let mut loader = Loader::default();
+    if let Some(position) = args.iter().position(|arg| arg == "--lenient") {
+        args.remove(position);
+        loader.lenient = true;
     }
+    if let Some(position) = args.iter().position(|arg| arg == "--input-format") {
+        args.remove(position);
+        if position == args.len() {
+            return Err(Error::MissingArgument("an input format"));
+        }
+        loader.format = Some(args.remove(position).parse()?);
+    }
+    Ok(loader)
 }
 
-
-fn read_lines<P>(filename: P) -> io::Result<impl Iterator<Item = String>>
-where
-    P: AsRef<Path>
-{
-    let file = File::open(filename)?;
-    let it = io::BufReader::new(file)
-        .lines()
-        .map(|a| a.expect("Bad line"));
-    Ok(it)
-}
\ No newline at end of file
+fn main() -> Result<(), Error> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let loader = loader(&mut args)?;
+
+    // Commands beyond the puzzle stages take their own arguments
+    let command = args.first().cloned();
+    let args = args.into_iter();
+    match command.as_deref() {
+        Some("top") => top(loader, args.skip(1)),
+        Some("balance") => balance(loader, args.skip(1)),
+        Some("stats") => stats(loader, args.skip(1)),
+        Some("stream") => stream(loader, args.skip(1)),
+        Some("target") => target(loader, args.skip(1)),
+        Some("diff") => diff(loader, args.skip(1)),
+        _ => solve(loader, args),
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt;
+
+use crate::ElfGroup;
+
+
+/// An elf identified by its position in the input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElfTotal {
+    pub index: usize,
+    pub total: u64,
+}
+
+pub struct CalorieStats {
+    pub elves_count: usize,
+    pub total: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub p90: u64,
+    pub p99: u64,
+    pub min: ElfTotal,
+    pub max: ElfTotal,
+    pub min_items: usize,
+    pub max_items: usize,
+    pub mean_items: f64,
+}
+
+/// Nearest-rank percentile of sorted `values`.
+fn percentile(sorted: &[u64], percent: f64) -> u64 {
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+impl fmt::Display for CalorieStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Elves:          {}", self.elves_count)?;
+        writeln!(f, "Total calories: {}", self.total)?;
+        writeln!(f, "Mean:           {:.1}", self.mean)?;
+        writeln!(f, "Median:         {:.1}", self.median)?;
+        writeln!(f, "90th pct:       {}", self.p90)?;
+        writeln!(f, "99th pct:       {}", self.p99)?;
+        writeln!(f, "Min:            {} (elf {})", self.min.total, self.min.index + 1)?;
+        writeln!(f, "Max:            {} (elf {})", self.max.total, self.max.index + 1)?;
+        write!(f, "Items per elf:  min {}, max {}, mean {:.1}", self.min_items, self.max_items, self.mean_items)
+    }
+}
+
+
+/// Totals from `start` included to `end` excluded, which may exceed `u32::MAX`.
+pub struct HistogramBin {
+    pub start: u64,
+    pub end: u64,
+    pub count: usize,
+}
+
+pub struct Histogram {
+    pub bins: Vec<HistogramBin>,
+}
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const BAR_WIDTH: usize = 50;
+        let max_count = self.bins.iter().map(|bin| bin.count).max().unwrap_or(0).max(1);
+        let label_width = self.bins.last().map_or(1, |bin| bin.end.to_string().len());
+        for (i, bin) in self.bins.iter().enumerate() {
+            if i > 0 { writeln!(f)?; }
+            let bar = "#".repeat((bin.count * BAR_WIDTH).div_ceil(max_count));
+            write!(f, "{:>w$} - {:>w$} | {bar} {}", bin.start, bin.end, bin.count, w = label_width)?;
+        }
+        Ok(())
+    }
+}
+
+
+impl ElfGroup {
+
+    pub fn stats(&self) -> Option<CalorieStats> {
+        let totals: Vec<u64> = self.elves.iter().map(|elf| elf.total_calories()).collect();
+        let mut sorted = totals.clone();
+        sorted.sort_unstable();
+        let elves_count = sorted.len();
+
+        let total: u64 = sorted.iter().sum();
+        let median = if elves_count.is_multiple_of(2) {
+            (*sorted.get(elves_count.checked_sub(2)? / 2)? as f64 + sorted[elves_count / 2] as f64) / 2.0
+        } else {
+            sorted[elves_count / 2] as f64
+        };
+        let elf_total = |(index, &total): (usize, &u64)| ElfTotal { index, total };
+        let min = totals.iter().enumerate().min_by_key(|(_, &total)| total).map(elf_total)?;
+        let max = totals.iter().enumerate().max_by_key(|(index, &total)| (total, std::cmp::Reverse(*index))).map(elf_total)?;
+        let items: Vec<usize> = self.elves.iter().map(|elf| elf.items_count()).collect();
+
+        Some(CalorieStats {
+            elves_count,
+            total,
+            mean: total as f64 / elves_count as f64,
+            median,
+            p90: percentile(&sorted, 90.0),
+            p99: percentile(&sorted, 99.0),
+            min,
+            max,
+            min_items: items.iter().copied().min()?,
+            max_items: items.iter().copied().max()?,
+            mean_items: items.iter().sum::<usize>() as f64 / elves_count as f64,
+        })
+    }
+
+    /// Splits the range of [`crate::Elf::total_calories`] in `bins_count` bins of equal width.
+    pub fn histogram(&self, bins_count: usize) -> Histogram {
+        let totals: Vec<u64> = self.elves.iter().map(|elf| elf.total_calories()).collect();
+        let (Some(&low), Some(&high)) = (totals.iter().min(), totals.iter().max()) else {
+            return Histogram { bins: Vec::new() };
+        };
+        let bins_count = bins_count.max(1);
+        let width = (high - low) / bins_count as u64 + 1;
+        let mut bins: Vec<HistogramBin> = (0..bins_count as u64)
+            .map(|i| HistogramBin { start: low + i * width, end: low + (i + 1) * width, count: 0 })
+            .collect();
+        for total in totals {
+            bins[((total - low) / width) as usize].count += 1;
+        }
+        Histogram { bins }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::lines_from_str;
+
+    use crate::ElfGroup;
+
+    #[test]
+    fn stats_of_an_odd_group() {
+        let stats = ElfGroup::parse(lines_from_str("7\n\n1\n\n3\n\n4\n5\n\n5")).unwrap().stats().unwrap();
+        assert_eq!((stats.total, stats.median, stats.mean), (25, 5.0, 5.0));
+        assert_eq!((stats.p90, stats.p99), (9, 9));
+        assert_eq!((stats.min.index, stats.min.total), (1, 1));
+        assert_eq!((stats.max.index, stats.max.total), (3, 9));
+        assert_eq!((stats.min_items, stats.max_items, stats.mean_items), (1, 2, 1.2));
+    }
+
+    #[test]
+    fn stats_of_an_even_group() {
+        let input: Vec<String> = (1..=20).rev().map(|total: u32| total.to_string()).collect();
+        let stats = ElfGroup::parse(lines_from_str(&input.join("\n\n"))).unwrap().stats().unwrap();
+        assert_eq!(stats.median, 10.5);
+        assert_eq!((stats.p90, stats.p99), (18, 20));
+        assert!(ElfGroup::new().stats().is_none());
+    }
+
+    #[test]
+    fn histogram_bins_cover_the_range() {
+        let histogram = ElfGroup::parse(lines_from_str("0\n\n1\n\n4\n\n5\n\n9\n\n10")).unwrap().histogram(2);
+        let bins: Vec<(u64, u64, usize)> = histogram.bins.iter().map(|bin| (bin.start, bin.end, bin.count)).collect();
+        assert_eq!(bins, [(0, 6, 4), (6, 12, 2)]);
+
+        let single = ElfGroup::parse(lines_from_str("3\n\n3")).unwrap().histogram(0);
+        assert_eq!(single.bins.len(), 1);
+        assert_eq!(single.bins[0].count, 2);
+        assert!(ElfGroup::new().histogram(3).bins.is_empty());
+    }
+
+    #[test]
+    fn histogram_edges_beyond_u32() {
+        let input = "0\n\n4294967295\n4294967295\n\n4294967295\n4294967294";
+        let histogram = ElfGroup::parse(lines_from_str(input)).unwrap().histogram(3);
+        let last = histogram.bins.last().unwrap();
+        assert!(last.end > 2 * u64::from(u32::MAX));
+        assert_eq!(last.count, 2);
+        assert_eq!(histogram.bins.iter().map(|bin| bin.count).sum::<usize>(), 3);
+    }
+}
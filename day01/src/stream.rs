@@ -0,0 +1,146 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+use aoc_common::{in_file, open_input, Diagnostic, Error};
+
+use crate::{InputFormat, Loader, ParseError};
+
+
+/// Folds an inventory line by line into running totals, keeping only the `k`
+/// best elves: memory stays constant whatever the size of the input.
+pub struct CalorieTally {
+    k: usize,
+    lenient: bool,
+    top: BinaryHeap<Reverse<(u64, Reverse<usize>)>>,
+    elves_count: usize,
+    current: u64,
+    pending: bool,
+    lines_count: usize,
+}
+
+impl CalorieTally {
+
+    /// Same rules as [`crate::ElfGroup::parse`]: only blank lines separate elves.
+    pub fn new(k: usize) -> CalorieTally {
+        CalorieTally {
+            k,
+            lenient: false,
+            top: BinaryHeap::with_capacity(k + 1),
+            elves_count: 0,
+            current: 0,
+            pending: false,
+            lines_count: 0,
+        }
+    }
+
+    /// Same rules as [`crate::ElfGroup::load`]: any line which is not a number
+    /// closes the current elf.
+    pub fn lenient(k: usize) -> CalorieTally {
+        CalorieTally { lenient: true, ..Self::new(k) }
+    }
+
+    /// Rejects a line which is neither calories nor blank, unless lenient.
+    pub fn push_line(&mut self, line: &str) -> Result<(), Diagnostic> {
+        self.lines_count += 1;
+        if self.lenient {
+            match line.parse::<u32>() {
+                Ok(calories) => self.add_calories(calories),
+                Err(_error) => self.close_elf(),
+            }
+            return Ok(());
+        }
+
+        let calories = line.trim();
+        if calories.is_empty() {
+            self.finish();
+            return Ok(());
+        }
+        match calories.parse() {
+            Ok(calories) => self.add_calories(calories),
+            Err(_error) => {
+                let start = line.len() - line.trim_start().len();
+                let span = start..start + calories.len();
+                return Err(Diagnostic::new(self.lines_count, line, ParseError::InvalidCalories { span }));
+            }
+        }
+        Ok(())
+    }
+
+    fn add_calories(&mut self, calories: u32) {
+        self.current += u64::from(calories);
+        self.pending = true;
+    }
+
+    /// Closes the last elf when the input does not end with a separator.
+    pub fn finish(&mut self) {
+        if self.pending {
+            self.close_elf();
+        }
+    }
+
+    fn close_elf(&mut self) {
+        self.top.push(Reverse((self.current, Reverse(self.elves_count))));
+        if self.top.len() > self.k {
+            self.top.pop();
+        }
+        self.elves_count += 1;
+        self.current = 0;
+        self.pending = false;
+    }
+
+    pub fn from_reader(reader: impl BufRead, k: usize) -> Result<CalorieTally, Error> {
+        CalorieTally::new(k).read(reader)
+    }
+
+    /// Tallies every line of `reader`, reporting all the invalid ones at the end.
+    pub fn read(mut self, reader: impl BufRead) -> Result<CalorieTally, Error> {
+        let mut diagnostics = Vec::new();
+        for line in reader.lines() {
+            if let Err(diagnostic) = self.push_line(&line?) {
+                diagnostics.push(diagnostic);
+            }
+        }
+        if !diagnostics.is_empty() {
+            return Err(Error::InvalidLines(diagnostics));
+        }
+        self.finish();
+        Ok(self)
+    }
+
+    pub fn elves_count(&self) -> usize {
+        self.elves_count
+    }
+
+    /// Input index and total of the best elves, most loaded first.
+    pub fn top(&self) -> Vec<(usize, u64)> {
+        let mut top: Vec<(usize, u64)> = self.top.iter()
+            .map(|Reverse((total, Reverse(index)))| (*index, *total))
+            .collect();
+        top.sort_by_key(|(index, total)| (Reverse(*total), *index));
+        top
+    }
+
+    /// Same answer as [`crate::ElfGroup::get_nth_max_calories`] for `k` elves.
+    pub fn max_calories(&self) -> Option<u64> {
+        if self.elves_count == 0 {
+            return None;
+        }
+        Some(self.top.iter().map(|Reverse((total, _))| total).sum())
+    }
+}
+
+
+impl Loader {
+
+    /// Streams the inventory at `path`, which must be in the blank-line format:
+    /// the other formats need the whole input to be read at once.
+    pub fn tally(&self, path: &str, k: usize) -> Result<CalorieTally, Error> {
+        let format = self.format.or_else(|| InputFormat::from_path(path)).unwrap_or(InputFormat::Blank);
+        if format != InputFormat::Blank {
+            return Err(Error::InvalidArgument(format!("{path}, only the blank-line format can be streamed")));
+        }
+        let tally = if self.lenient { CalorieTally::lenient(k) } else { CalorieTally::new(k) };
+        tally.read(open_input(path)?).map_err(|error| in_file(error, path))
+    }
+}
@@ -0,0 +1,145 @@
+use std::cmp::Reverse;
+
+use crate::{Elf, ElfGroup, ElfTotal};
+
+
+const UNREACHED: u32 = u32::MAX;
+
+
+impl ElfGroup {
+
+    fn totals(&self) -> Vec<ElfTotal> {
+        self.elves.iter()
+            .enumerate()
+            .map(|(index, elf)| ElfTotal { index, total: elf.total_calories() })
+            .collect()
+    }
+
+    /// The fewest elves whose combined calories reach `target`: taking the most
+    /// loaded elves first is optimal. `None` when the whole group falls short.
+    pub fn smallest_set_reaching(&self, target: u64) -> Option<Vec<ElfTotal>> {
+        let mut totals = self.totals();
+        totals.sort_by_key(|elf| (Reverse(elf.total), elf.index));
+
+        let mut selected = Vec::new();
+        let mut sum = 0;
+        for elf in totals {
+            if sum >= target {
+                break;
+            }
+            sum += elf.total;
+            selected.push(elf);
+        }
+        (sum >= target).then_some(selected)
+    }
+
+    pub fn total_calories(&self) -> u64 {
+        self.elves.iter().map(Elf::total_calories).sum()
+    }
+
+    /// Elves whose combined calories are exactly `target`, by subset-sum dynamic
+    /// programming. The most loaded elves are tried first, which favours small
+    /// subsets, and sums beyond `target` or beyond what is reachable are skipped.
+    /// Memory grows with `target`, so targets above [`Self::total_calories`]
+    /// are answered without running the search.
+    pub fn subset_hitting(&self, target: u64) -> Option<Vec<ElfTotal>> {
+        if target == 0 {
+            return Some(Vec::new());
+        }
+        if target > self.total_calories() {
+            return None;
+        }
+        let mut totals = self.totals();
+        totals.retain(|elf| elf.total > 0 && elf.total <= target);
+        let grand_total: u64 = totals.iter().map(|elf| elf.total).sum();
+        if grand_total < target {
+            return None;
+        }
+        if grand_total == target {
+            return Some(totals);
+        }
+        totals.sort_by_key(|elf| (Reverse(elf.total), elf.index));
+
+        // `reached_by[sum]` is the first elf completing a subset of that sum: the
+        // rest of the subset only uses elves considered before it
+        let target = usize::try_from(target).ok()?;
+        let mut reached_by = vec![UNREACHED; target + 1];
+        let mut reachable_max = 0;
+        for (position, elf) in totals.iter().enumerate() {
+            let weight = elf.total as usize;
+            let highest = target.min(reachable_max + weight);
+            for sum in (weight.max(1)..=highest).rev() {
+                let previous = sum - weight;
+                if reached_by[sum] == UNREACHED && (previous == 0 || reached_by[previous] != UNREACHED) {
+                    reached_by[sum] = position as u32;
+                }
+            }
+            reachable_max = highest;
+            if reached_by[target] != UNREACHED {
+                break;
+            }
+        }
+
+        let mut subset = Vec::new();
+        let mut sum = target;
+        while sum > 0 {
+            let position = reached_by[sum];
+            if position == UNREACHED {
+                return None;
+            }
+            let elf = totals[position as usize];
+            subset.push(elf);
+            sum -= elf.total as usize;
+        }
+        Some(subset)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::lines_from_str;
+
+    use crate::{ElfGroup, ElfTotal};
+
+    fn indices(selected: Option<Vec<ElfTotal>>) -> Option<Vec<usize>> {
+        let mut indices: Vec<usize> = selected?.iter().map(|elf| elf.index).collect();
+        indices.sort_unstable();
+        Some(indices)
+    }
+
+    #[test]
+    fn subset_hits_the_target_exactly() {
+        let elf_group = ElfGroup::parse(lines_from_str("4\n\n6\n\n9\n\n3")).unwrap();
+        assert_eq!(indices(elf_group.subset_hitting(13)), Some(vec![0, 2]));
+        assert_eq!(indices(elf_group.subset_hitting(22)), Some(vec![0, 1, 2, 3]));
+        assert_eq!(indices(elf_group.subset_hitting(3)), Some(vec![3]));
+    }
+
+    #[test]
+    fn subset_may_not_exist() {
+        let elf_group = ElfGroup::parse(lines_from_str("4\n\n6\n\n9")).unwrap();
+        assert_eq!(indices(elf_group.subset_hitting(5)), None);
+        assert_eq!(indices(elf_group.subset_hitting(18)), None);
+        assert_eq!(indices(elf_group.subset_hitting(u64::MAX)), None);
+        assert_eq!(indices(elf_group.smallest_set_reaching(20)), None);
+    }
+
+    #[test]
+    fn zero_target_needs_no_elves() {
+        let elf_group = ElfGroup::parse(lines_from_str("4\n\n6")).unwrap();
+        assert_eq!(indices(elf_group.subset_hitting(0)), Some(vec![]));
+        assert_eq!(indices(elf_group.smallest_set_reaching(0)), Some(vec![]));
+        assert_eq!(indices(ElfGroup::new().subset_hitting(0)), Some(vec![]));
+    }
+
+    #[test]
+    fn zero_calorie_elves_are_left_out() {
+        let empty_elves = ElfGroup::parse(lines_from_str("0\n\n0")).unwrap();
+        assert_eq!(indices(empty_elves.subset_hitting(1)), None);
+        let elf_group = ElfGroup::parse(lines_from_str("0\n\n5\n\n0\n\n3")).unwrap();
+        assert_eq!(indices(elf_group.subset_hitting(8)), Some(vec![1, 3]));
+        assert_eq!(indices(elf_group.subset_hitting(5)), Some(vec![1]));
+        assert_eq!(indices(elf_group.smallest_set_reaching(6)), Some(vec![1, 3]));
+    }
+}
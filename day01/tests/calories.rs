@@ -0,0 +1,112 @@
+use std::io::Cursor;
+
+use aoc_common::{lines_from_str, Error, Solver, Stage};
+
+use day01::{CalorieTally, Elf, ElfGroup, InputFormat, Loader, Puzzle};
+
+
+#[test]
+fn strict_calories_report_every_invalid_line() {
+    let answer = Puzzle.solve_str(Stage::Two, "1000\n\n2000\n\n3000").expect("Unable to solve");
+    assert_eq!(answer.to_string(), "6000");
+
+    match Puzzle.solve_str(Stage::One, "1000\nabc\n\n2000\n4x") {
+        Err(Error::InvalidLines(diagnostics)) => {
+            let lines: Vec<usize> = diagnostics.iter().map(|diagnostic| diagnostic.line).collect();
+            assert_eq!(lines, [2, 5]);
+        }
+        other => panic!("expected invalid lines, got {other:?}"),
+    }
+    let lenient = Loader { lenient: true, ..Default::default() };
+    let answer = lenient.solve_str(Stage::One, "1000\nabc\n2000\n4x").expect("Unable to solve");
+    assert_eq!(answer.to_string(), "2000");
+}
+
+#[test]
+fn streamed_calories_are_strict_unless_lenient() {
+    let input = "1000\nabc\n\n\n2000\n4x";
+    match CalorieTally::from_reader(Cursor::new(input), 1).err() {
+        Some(Error::InvalidLines(diagnostics)) => {
+            let lines: Vec<usize> = diagnostics.iter().map(|diagnostic| diagnostic.line).collect();
+            assert_eq!(lines, [2, 6]);
+        }
+        other => panic!("expected invalid lines, got {other:?}"),
+    }
+    let tally = CalorieTally::from_reader(Cursor::new("1000\n\n\n2000\n"), 3).expect("Unable to stream");
+    assert_eq!(tally.elves_count(), 2);
+    let tally = CalorieTally::lenient(1).read(Cursor::new(input)).expect("Unable to stream");
+    assert_eq!(tally.max_calories(), Some(2000));
+}
+
+#[test]
+fn streamed_calories_do_not_overflow() {
+    let tally = CalorieTally::from_reader(Cursor::new("4000000000\n4000000000\n"), 1).expect("Unable to stream");
+    assert_eq!(tally.max_calories(), Some(8_000_000_000));
+    let input = "3000000000\n\n3000000000\n\n1\n";
+    let tally = CalorieTally::from_reader(Cursor::new(input), 3).expect("Unable to stream");
+    assert_eq!(tally.max_calories(), Some(6_000_000_001));
+    assert_eq!(Puzzle.solve_str(Stage::Two, input).expect("Unable to solve").to_string(), "6000000001");
+}
+
+#[test]
+fn calories_formats_agree() {
+    let blank = "1000\n2000\n\n4000\n\n5000\n6000";
+    let csv = "elf_id,calories\nalice,1000\nbob,4000\nalice,2000\ncarol,5000\ncarol,6000";
+    let json = r#"[[1000, 2000], {"id": "bob", "calories": [4000]}, [5000, 6000]]"#;
+    for stage in [Stage::One, Stage::Two] {
+        let expected = Puzzle.solve_str(stage, blank).expect("Unable to solve");
+        assert_eq!(Puzzle.solve_str(stage, csv).expect("Unable to solve CSV"), expected);
+        assert_eq!(Puzzle.solve_str(stage, json).expect("Unable to solve JSON"), expected);
+    }
+
+    let elf_group = ElfGroup::from_csv(lines_from_str(csv)).expect("Unable to read CSV");
+    let ids: Vec<Option<&str>> = elf_group.elves().iter().map(Elf::id).collect();
+    assert_eq!(ids, [Some("alice"), Some("bob"), Some("carol")]);
+}
+
+#[test]
+fn calories_formats_report_their_errors() {
+    let elf_group = ElfGroup::from_csv(lines_from_str("\nelf_id,calories\nalice,1000")).expect("Unable to read CSV");
+    assert_eq!(elf_group.elves().len(), 1);
+
+    match ElfGroup::from_json("\n  {\"id\": \"alice\"}").err() {
+        Some(Error::Parse(diagnostic)) => assert_eq!((diagnostic.line, diagnostic.column()), (2, 3)),
+        other => panic!("expected a diagnostic, got {other:?}"),
+    }
+    match ElfGroup::from_json("[\n  [1000],\n  [\"}\", 2000], {\"id\": \"bob\"}\n]").err() {
+        Some(Error::Parse(diagnostic)) => assert_eq!((diagnostic.line, diagnostic.column()), (3, 3)),
+        other => panic!("expected a diagnostic, got {other:?}"),
+    }
+    match ElfGroup::from_json("[[1000], {\"id\": [\"bob\"], \"calories\": [1]}]").err() {
+        Some(Error::Parse(diagnostic)) => assert_eq!((diagnostic.line, diagnostic.column()), (1, 10)),
+        other => panic!("expected a diagnostic, got {other:?}"),
+    }
+
+    let loader = Loader { format: Some(InputFormat::Json), ..Default::default() };
+    assert!(matches!(loader.tally("test.txt", 1), Err(Error::InvalidArgument(_))));
+    assert!(matches!(Loader::default().tally("inventory.csv", 1), Err(Error::InvalidArgument(_))));
+}
+
+#[test]
+fn calories_snapshots_diff() {
+    let load = |csv| ElfGroup::from_csv(lines_from_str(csv)).expect("Unable to read CSV");
+    let before = load("a,100\nb,300\na,200\nc,50");
+    let after = load("a,100\nb,300\nc,50\nc,500\nd,10");
+    let diff = before.diff(&after, 2);
+
+    let changes: Vec<(&str, i64)> = diff.changes.iter().map(|change| (change.elf.as_str(), change.delta())).collect();
+    assert_eq!(changes, [("a", -200), ("c", 500), ("d", 10)]);
+    assert_eq!(diff.changes[0].removed, [200]);
+    let ranks: Vec<(&str, Option<usize>, Option<usize>)> = diff.ranks.iter()
+        .map(|change| (change.elf.as_str(), change.before, change.after))
+        .collect();
+    assert_eq!(ranks, [("c", None, Some(1)), ("a", Some(1), None)]);
+    assert!(before.diff(&before, 2).is_empty());
+
+    let json = r#"[{"id": "a", "calories": [100]}, [50], {"id": "a", "calories": [200]}]"#;
+    let merged = ElfGroup::from_json(json).expect("Unable to read JSON");
+    assert_eq!(merged.elves().len(), 2);
+    let diff = before.diff(&merged, 1);
+    let changes: Vec<(&str, Option<u64>)> = diff.changes.iter().map(|change| (change.elf.as_str(), change.after)).collect();
+    assert_eq!(changes, [("b", None), ("c", None), ("#2", Some(50))]);
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+
+use crate::{Move, OpponentModel, Round, RoundResult, Ruleset, Rng};
+
+
+/// A computer player choosing its moves with one of several policies.
+pub enum Bot {
+    /// Always plays the same move.
+    Constant(Move),
+    /// Plays every move in turn, in rules order.
+    Cyclic { position: usize },
+    /// Plays uniformly at random.
+    Random(Rng),
+    /// Keeps a winning move, otherwise plays what beats the opponent's last move.
+    WinStayLoseShift { last: Option<(Move, Move)> },
+    /// Plays the opponent column of a strategy guide, over and over.
+    Replay { moves: Vec<Move>, position: usize },
+    /// Counters the move its opponent plays most often.
+    Adaptive(OpponentModel),
+}
+
+impl Bot {
+
+    pub fn random(seed: u64) -> Bot {
+        Bot::Random(Rng::new(seed))
+    }
+
+    /// Replays the opponent moves of `rounds`, which must not be empty.
+    pub fn replay(rounds: &[Round]) -> Bot {
+        Bot::Replay { moves: rounds.iter().map(Round::opponent_move).collect(), position: 0 }
+    }
+
+    pub fn adaptive(rules: &Ruleset) -> Bot {
+        Bot::Adaptive(OpponentModel::new(rules, 0))
+    }
+
+    pub fn play(&mut self, rules: &Ruleset) -> Move {
+        let first = rules.moves().next().expect("Rulesets have moves");
+        match self {
+            Self::Constant(m) => *m,
+            Self::Cyclic { position } => {
+                let m = rules.moves().nth(*position % rules.moves().count()).unwrap_or(first);
+                *position += 1;
+                m
+            }
+            Self::WinStayLoseShift { last } => match *last {
+                None => first,
+                Some((own, opponent)) if rules.beats(own, opponent) => own,
+                Some((_, opponent)) => rules.for_expected_result(opponent, RoundResult::Victory),
+            },
+            Self::Random(rng) => rules.moves().nth(rng.below(rules.moves().count())).expect("Rulesets have moves"),
+            Self::Replay { moves, position } => {
+                let m = moves[*position % moves.len()];
+                *position += 1;
+                m
+            }
+            Self::Adaptive(model) => match model.predict() {
+                Some(prediction) => rules.for_expected_result(prediction, RoundResult::Victory),
+                None => first,
+            },
+        }
+    }
+
+    /// Lets the bot learn from the round it just played.
+    pub fn observe(&mut self, own_move: Move, opponent_move: Move) {
+        match self {
+            Self::Adaptive(model) => model.observe(opponent_move),
+            Self::WinStayLoseShift { last } => *last = Some((own_move, opponent_move)),
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Display for Bot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constant(_) => write!(f, "constant"),
+            Self::Cyclic { .. } => write!(f, "cyclic"),
+            Self::Random(_) => write!(f, "random"),
+            Self::WinStayLoseShift { .. } => write!(f, "win-stay/lose-shift"),
+            Self::Replay { .. } => write!(f, "replay"),
+            Self::Adaptive(_) => write!(f, "adaptive"),
+        }
+    }
+}
+
+
+/// The outcome of one round of a [`Session`], from the player's point of view.
+pub struct Turn {
+    pub bot_move: Move,
+    pub result: RoundResult,
+    pub score: u32,
+    pub total: u32,
+}
+
+/// A game played live against a [`Bot`], keeping every round for the transcript.
+pub struct Session {
+    rules: Ruleset,
+    bot: Bot,
+    rounds: Vec<Round>,
+    total: u32,
+}
+
+impl Session {
+
+    pub fn new(rules: &Ruleset, bot: Bot) -> Session {
+        Session { rules: rules.clone(), bot, rounds: Vec::new(), total: 0 }
+    }
+
+    pub fn play(&mut self, my_move: Move) -> Turn {
+        let bot_move = self.bot.play(&self.rules);
+        self.bot.observe(bot_move, my_move);
+
+        let round = Round::new(bot_move, my_move);
+        let score = round.score(&self.rules);
+        self.total += score;
+        self.rounds.push(round);
+        Turn {
+            bot_move,
+            result: self.rules.confrontation_result(my_move, bot_move),
+            score,
+            total: self.total,
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// The rounds played, in the strategy guide format read by stage 1.
+    pub fn transcript(&self) -> String {
+        self.rounds.iter()
+            .map(|round| {
+                let bot = self.rules.rule(round.opponent_move()).opponent_id;
+                let me = self.rules.rule(round.my_move()).self_id;
+                format!("{bot} {me}\n")
+            })
+            .collect()
+    }
+}
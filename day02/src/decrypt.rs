@@ -0,0 +1,167 @@
+use std::fmt;
+
+use aoc_common::{Diagnostic, Stage};
+
+use crate::{split_ids, Game, Move, ParseError, Round, RoundResult, Ruleset};
+
+
+/// A strategy guide whose second column has not been deciphered yet.
+pub struct EncryptedGuide {
+    rules: Ruleset,
+    rounds: Vec<(Move, char)>,
+}
+
+/// One way of reading the second column of a strategy guide.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Interpretation {
+    Moves(Vec<(char, Move)>),
+    Results(Vec<(char, RoundResult)>),
+}
+
+pub struct Hypothesis {
+    pub interpretation: Interpretation,
+    pub total_score: u32,
+    /// The puzzle stage reading the guide this way, if any.
+    pub stage: Option<Stage>,
+}
+
+/// Every [`Interpretation`] of a guide, best total score first.
+pub struct Decryption {
+    rules: Ruleset,
+    pub hypotheses: Vec<Hypothesis>,
+}
+
+/// All the ways of giving each of `count` letters a different item.
+fn arrangements<T: Copy>(items: &[T], count: usize) -> Vec<Vec<T>> {
+    if count == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        let rest: Vec<T> = items.iter().enumerate().filter(|(other, _)| *other != index).map(|(_, item)| *item).collect();
+        for mut arrangement in arrangements(&rest, count - 1) {
+            arrangement.insert(0, *item);
+            result.push(arrangement);
+        }
+    }
+    result
+}
+
+impl EncryptedGuide {
+
+    pub fn from_lines(lines: impl Iterator<Item = String>, rules: &Ruleset) -> Result<EncryptedGuide, Diagnostic> {
+        let mut rounds = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let ids = split_ids(&line).and_then(|[(column, opponent_id), (_, letter)]| {
+                let opponent_move = rules.find(opponent_id)
+                    .ok_or(ParseError::InvalidMove { id: opponent_id, column })?;
+                Ok((opponent_move, letter))
+            });
+            rounds.push(ids.map_err(|error| Diagnostic::new(index + 1, &line, error))?);
+        }
+        Ok(EncryptedGuide { rules: rules.clone(), rounds })
+    }
+
+    /// The distinct letters of the second column, in alphabetical order.
+    pub fn letters(&self) -> Vec<char> {
+        let mut letters: Vec<char> = self.rounds.iter().map(|(_, letter)| *letter).collect();
+        letters.sort_unstable();
+        letters.dedup();
+        letters
+    }
+
+    /// Every mapping of the letters to distinct moves, then to distinct round results.
+    pub fn interpretations(&self) -> Vec<Interpretation> {
+        let letters = self.letters();
+        let moves: Vec<Move> = self.rules.moves().collect();
+        let as_moves = arrangements(&moves, letters.len()).into_iter()
+            .map(|moves| Interpretation::Moves(letters.iter().copied().zip(moves).collect()));
+        let results = if letters.len() <= RoundResult::all().len() {
+            arrangements(&RoundResult::all(), letters.len())
+        } else {
+            Vec::new()
+        };
+        let as_results = results.into_iter()
+            .map(|results| Interpretation::Results(letters.iter().copied().zip(results).collect()));
+        as_moves.chain(as_results).collect()
+    }
+
+    pub fn game(&self, interpretation: &Interpretation) -> Game {
+        let rounds = self.rounds.iter()
+            .map(|(opponent_move, letter)| {
+                let my_move = match interpretation {
+                    Interpretation::Moves(moves) => moves.iter()
+                        .find(|(other, _)| other == letter)
+                        .map(|(_, m)| *m),
+                    Interpretation::Results(results) => results.iter()
+                        .find(|(other, _)| other == letter)
+                        .map(|(_, result)| self.rules.for_expected_result(*opponent_move, *result)),
+                };
+                Round::new(*opponent_move, my_move.expect("Interpretations map every letter of the guide"))
+            })
+            .collect();
+        Game::new(&self.rules, rounds)
+    }
+
+    /// Which puzzle stage reads the guide with `interpretation`.
+    fn stage(&self, interpretation: &Interpretation) -> Option<Stage> {
+        let is_stage = match interpretation {
+            Interpretation::Moves(moves) => moves.iter().all(|(letter, m)| self.rules.find_self(*letter) == Some(*m)),
+            Interpretation::Results(results) => results.iter().all(|(letter, result)| RoundResult::from(*letter) == Some(*result)),
+        };
+        match interpretation {
+            _ if !is_stage => None,
+            Interpretation::Moves(_) => Some(Stage::One),
+            Interpretation::Results(_) => Some(Stage::Two),
+        }
+    }
+
+    /// Scores every interpretation, best first.
+    pub fn decrypt(&self) -> Decryption {
+        let mut hypotheses: Vec<Hypothesis> = self.interpretations().into_iter()
+            .map(|interpretation| Hypothesis {
+                total_score: self.game(&interpretation).total_score(),
+                stage: self.stage(&interpretation),
+                interpretation,
+            })
+            .collect();
+        hypotheses.sort_by_key(|hypothesis| std::cmp::Reverse(hypothesis.total_score));
+        Decryption { rules: self.rules.clone(), hypotheses }
+    }
+}
+
+impl Decryption {
+
+    /// Keeps the interpretations giving `total_score`.
+    pub fn matching(self, total_score: u32) -> Decryption {
+        let hypotheses = self.hypotheses.into_iter()
+            .filter(|hypothesis| hypothesis.total_score == total_score)
+            .collect();
+        Decryption { rules: self.rules, hypotheses }
+    }
+}
+
+impl fmt::Display for Decryption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.hypotheses.is_empty() {
+            return writeln!(f, "No interpretation of the guide matches");
+        }
+        for (rank, hypothesis) in self.hypotheses.iter().enumerate() {
+            write!(f, "#{:<3} {:>8}  ", rank + 1, hypothesis.total_score)?;
+            let mapping: Vec<String> = match &hypothesis.interpretation {
+                Interpretation::Moves(moves) => moves.iter()
+                    .map(|(letter, m)| format!("{letter}={}", self.rules.rule(*m).name))
+                    .collect(),
+                Interpretation::Results(results) => results.iter()
+                    .map(|(letter, result)| format!("{letter}={result}"))
+                    .collect(),
+            };
+            write!(f, "{}", mapping.join(" "))?;
+            if let Some(stage) = hypothesis.stage {
+                write!(f, "  ({stage})")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
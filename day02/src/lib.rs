@@ -0,0 +1,265 @@
+mod bot;
+mod decrypt;
+mod model;
+mod random;
+mod rules;
+mod scoring;
+mod tournament;
+mod trace;
+
+use std::fmt;
+use std::ops::Range;
+
+use aoc_common::{Answer, Diagnostic, Error, Reason, Solver, Stage};
+
+pub use bot::{Bot, Session, Turn};
+pub use decrypt::{Decryption, EncryptedGuide, Hypothesis, Interpretation};
+pub use model::{ModelReport, OpponentAnalysis, OpponentModel};
+pub use random::Rng;
+pub use rules::{Move, MoveRule, Ruleset, RulesError};
+pub use scoring::Scoring;
+pub use tournament::{Standing, Strategy, Tournament};
+pub use trace::{MovePoints, RoundTrace, Trace, TraceSummary};
+
+
+pub enum GameMode {
+    Simple,
+    ExpectedResult
+}
+
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundResult {
+    Defeat,
+    Draw,
+    Victory
+}
+
+impl RoundResult {
+
+    pub fn from(id: char) -> Option<RoundResult> {
+        if id == RoundResult::Defeat.id() { return Some(RoundResult::Defeat); };
+        if id == RoundResult::Draw.id() { return Some(RoundResult::Draw); };
+        if id == RoundResult::Victory.id() { return Some(RoundResult::Victory); };
+        None
+    }
+
+    pub fn id(&self) -> char {
+        match self {
+            Self::Defeat => 'X',
+            Self::Draw => 'Y',
+            Self::Victory => 'Z',
+        }
+    }
+
+    pub fn all() -> [RoundResult; 3] {
+        [Self::Defeat, Self::Draw, Self::Victory]
+    }
+
+    /// Default points of the result, see [`Ruleset::outcome_points`].
+    pub fn value(&self) -> u32 {
+        match self {
+            Self::Defeat => 0,
+            Self::Draw => 3,
+            Self::Victory => 6
+        }
+    }
+}
+
+impl std::str::FromStr for RoundResult {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "defeat" => Ok(Self::Defeat),
+            "draw" => Ok(Self::Draw),
+            "victory" => Ok(Self::Victory),
+            _ => Err(Error::InvalidArgument(String::from(s))),
+        }
+    }
+}
+
+impl fmt::Display for RoundResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Defeat => write!(f, "defeat"),
+            Self::Draw => write!(f, "draw"),
+            Self::Victory => write!(f, "victory"),
+        }
+    }
+}
+
+
+#[derive(Debug)]
+pub enum ParseError {
+    MissingId { column: usize },
+    InvalidMove { id: char, column: usize },
+    InvalidResult { id: char, column: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingId { .. } => write!(f, "Expecting a move or result id"),
+            Self::InvalidMove { id, .. } => write!(f, "Invalid move id '{id}' for these rules"),
+            Self::InvalidResult { id, .. } => write!(f, "Invalid round result id '{id}', expecting X, Y or Z"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Reason for ParseError {
+    fn span(&self) -> Option<Range<usize>> {
+        let column = match self {
+            Self::MissingId { column }
+            | Self::InvalidMove { column, .. }
+            | Self::InvalidResult { column, .. } => *column,
+        };
+        Some(column..column + 1)
+    }
+}
+
+
+/// The column and letter of both ids of a strategy guide line.
+fn split_ids(line: &str) -> Result<[(usize, char); 2], ParseError> {
+    let mut offset = 0;
+    let mut move_ids = line.split(' ').map(|s| {
+        let column = offset;
+        offset += s.len() + 1;
+        (column, s.chars().next())
+    });
+    let mut next_id = || match move_ids.next() {
+        Some((column, Some(id))) => Ok((column, id)),
+        Some((column, None)) => Err(ParseError::MissingId { column }),
+        None => Err(ParseError::MissingId { column: line.len() }),
+    };
+    Ok([next_id()?, next_id()?])
+}
+
+
+#[derive(Clone)]
+pub struct Round {
+    opponent_move: Move,
+    my_move: Move,
+}
+
+impl Round {
+
+    pub fn new(opponent_move: Move, my_move: Move) -> Round {
+        Round { opponent_move, my_move }
+    }
+
+    pub fn from_line(line: &str, rules: &Ruleset, game_mode: &GameMode) -> Result<Round, ParseError> {
+        let [(opponent_column, opponent_move_id), (my_column, my_id)] = split_ids(line)?;
+
+        let opponent_move = rules.find(opponent_move_id)
+            .ok_or(ParseError::InvalidMove { id: opponent_move_id, column: opponent_column })?;
+        let my_move = match game_mode {
+            GameMode::Simple => {
+                rules.find(my_id).ok_or(ParseError::InvalidMove { id: my_id, column: my_column })?
+            }
+            GameMode::ExpectedResult => {
+                let expected_result = RoundResult::from(my_id)
+                    .ok_or(ParseError::InvalidResult { id: my_id, column: my_column })?;
+                rules.for_expected_result(opponent_move, expected_result)
+            }
+        };
+        Ok(Round::new(opponent_move, my_move))
+    }
+
+    pub fn opponent_move(&self) -> Move {
+        self.opponent_move
+    }
+
+    pub fn my_move(&self) -> Move {
+        self.my_move
+    }
+
+    pub fn score(&self, rules: &Ruleset) -> u32 {
+        rules.inherent_score(self.my_move) +
+            rules.outcome_points(rules.confrontation_result(self.my_move, self.opponent_move))
+    }
+}
+
+
+pub struct Game {
+    rules: Ruleset,
+    rounds: Vec<Round>,
+}
+
+impl Game {
+
+    pub fn new(rules: &Ruleset, rounds: Vec<Round>) -> Game {
+        Game { rules: rules.clone(), rounds }
+    }
+
+    pub fn from_lines(lines: impl Iterator<Item = String>, rules: &Ruleset, game_mode: &GameMode) -> Result<Game, Diagnostic> {
+        let mut game = Game { rules: rules.clone(), rounds: Vec::new() };
+        for (index, line) in lines.enumerate() {
+            let round = Round::from_line(line.as_str(), rules, game_mode)
+                .map_err(|error| Diagnostic::new(index + 1, &line, error))?;
+            game.rounds.push(round);
+        }
+        Ok(game)
+    }
+
+    pub fn rules(&self) -> &Ruleset {
+        &self.rules
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    pub fn total_score(&self) -> u32{
+        self.rounds
+            .iter()
+            .fold(0, |acc, round|
+                acc + round.score(&self.rules)
+            )
+    }
+}
+
+
+pub fn game_mode_for(stage: Stage) -> GameMode {
+    match stage {
+        Stage::One => GameMode::Simple,
+        Stage::Two => GameMode::ExpectedResult,
+    }
+}
+
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    type Input = Game;
+
+    fn parse(&self, stage: Stage, lines: impl Iterator<Item = String>) -> Result<Game, Error> {
+        Ruleset::rock_paper_scissors().parse(stage, lines)
+    }
+
+    fn part1(&self, game: Game) -> Result<Answer, Error> {
+        Ok(game.total_score().into())
+    }
+
+    fn part2(&self, game: Game) -> Result<Answer, Error> {
+        Ok(game.total_score().into())
+    }
+}
+
+/// [`Puzzle`] played with other rules.
+impl Solver for Ruleset {
+    type Input = Game;
+
+    fn parse(&self, stage: Stage, lines: impl Iterator<Item = String>) -> Result<Game, Error> {
+        Ok(Game::from_lines(lines, self, &game_mode_for(stage))?)
+    }
+
+    fn part1(&self, game: Game) -> Result<Answer, Error> {
+        Puzzle.part1(game)
+    }
+
+    fn part2(&self, game: Game) -> Result<Answer, Error> {
+        Puzzle.part2(game)
+    }
+}
@@ -1,234 +1,230 @@
-use std::cmp::Ordering;
-use std::fs::File;
-use std::io::{self, BufRead};
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::{self, BufRead, Write};
 use std::path::Path;
 
+use aoc_common::{process_lines, read_lines, run_day_with, Args, Error, Stage};
 
-pub enum GameMode {
-    Simple,
-    ExpectedResult
-}
+use day02::{game_mode_for, Bot, EncryptedGuide, Game, OpponentAnalysis, Rng, Ruleset, Scoring, Session, Strategy, Tournament};
 
 
-pub enum RoundResult {
-    Defeat,
-    Draw,
-    Victory
+/// Takes out `flag` and its value from the arguments.
+fn take_option(args: &mut Vec<String>, flag: &str, what: &'static str) -> Result<Option<String>, Error> {
+    let Some(position) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+    args.remove(position);
+    if position == args.len() {
+        return Err(Error::MissingArgument(what));
+    }
+    Ok(Some(args.remove(position)))
 }
 
-impl RoundResult {
-
-    pub fn from(id: char) -> Option<RoundResult> {
-        if id == RoundResult::Defeat.id() { return Some(RoundResult::Defeat); };
-        if id == RoundResult::Draw.id() { return Some(RoundResult::Draw); };
-        if id == RoundResult::Victory.id() { return Some(RoundResult::Victory); };
-        None
+/// Takes out the `--rules <rps|rpsls|file>`, `--scoring <file>` and
+/// `--points <name=points,...>` options understood by every command.
+fn rules(args: &mut Vec<String>) -> Result<Ruleset, Error> {
+    let rules = match take_option(args, "--rules", "a ruleset")? {
+        Some(name) => Ruleset::named(&name)?,
+        None => Ruleset::rock_paper_scissors(),
+    };
+    let mut scoring = match take_option(args, "--scoring", "a scoring file")? {
+        Some(path) => Scoring::load(path)?,
+        None => Scoring::default(),
+    };
+    if let Some(points) = take_option(args, "--points", "points such as draw=1")? {
+        let points = Scoring::from_points(&points).map_err(|error| Error::Solver(Box::new(error)))?;
+        scoring = scoring.merged(points);
     }
+    rules.with_scoring(&scoring).map_err(|error| Error::Solver(Box::new(error)))
+}
 
-    pub fn id(&self) -> char {
-        match self {
-            Self::Defeat => 'X',
-            Self::Draw => 'Y',
-            Self::Victory => 'Z',
+fn decrypt(rules: Ruleset, mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut path = None;
+    let mut known_score = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--score" => {
+                let value = args.next().ok_or(Error::MissingArgument("a total score"))?;
+                known_score = Some(value.parse().map_err(|_| Error::InvalidArgument(value))?);
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(Error::InvalidArgument(arg)),
         }
     }
+    let path = path.ok_or(Error::MissingArgument("a file name"))?;
 
-    pub fn value(&self) -> u32 {
-        match self {
-            Self::Defeat => 0,
-            Self::Draw => 3,
-            Self::Victory => 6
-        }
+    let guide = process_lines(read_lines(&path)?, |lines| {
+        Ok(EncryptedGuide::from_lines(lines, &rules).map_err(|diagnostic| diagnostic.in_file(&path))?)
+    })?;
+    let decryption = guide.decrypt();
+    match known_score {
+        Some(score) => print!("{}", decryption.matching(score)),
+        None => print!("{decryption}"),
     }
+    Ok(())
 }
 
-
-#[derive(PartialEq, Eq)]
-pub enum Move {
-    Rock,
-    Paper,
-    Scissors,
-}
-
-impl Move {
-
-    pub fn from(id: char) -> Option<Move> {
-        if id == Self::Rock.self_id() { return Some(Self::Rock) }
-        if id == Self::Paper.self_id() { return Some(Self::Paper) }
-        if id == Self::Scissors.self_id() { return Some(Self::Scissors) }
-        if id == Self::Rock.opponent_id() { return Some(Self::Rock) }
-        if id == Self::Paper.opponent_id() { return Some(Self::Paper) }
-        if id == Self::Scissors.opponent_id() { return Some(Self::Scissors) }
-        None
-    }
-
-    pub fn for_expected_result(opponent_move: &Move, expected_result: RoundResult) -> Move {
-        match (opponent_move, expected_result) {
-            (Self::Rock, RoundResult::Defeat) => Self::Scissors,
-            (Self::Rock, RoundResult::Draw) => Self::Rock,
-            (Self::Rock, RoundResult::Victory) => Self::Paper,
-            (Self::Paper, RoundResult::Defeat) => Self::Rock,
-            (Self::Paper, RoundResult::Draw) => Self::Paper,
-            (Self::Paper, RoundResult::Victory) => Self::Scissors,
-            (Self::Scissors, RoundResult::Defeat) => Self::Paper,
-            (Self::Scissors, RoundResult::Draw) => Self::Scissors,
-            (Self::Scissors, RoundResult::Victory) => Self::Rock,
+fn analyse(rules: Ruleset, mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut path = None;
+    let mut max_order = 2;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--order" => {
+                let value = args.next().ok_or(Error::MissingArgument("a model order"))?;
+                max_order = value.parse().map_err(|_| Error::InvalidArgument(value))?;
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(Error::InvalidArgument(arg)),
         }
     }
+    let path = path.ok_or(Error::MissingArgument("a file name"))?;
 
-    pub fn opponent_id(&self) -> char {
-        match self {
-            Self::Rock => 'A',
-            Self::Paper => 'B',
-            Self::Scissors => 'C',
-        }
-    }
+    let lines = read_lines(&path)?.collect::<Result<Vec<String>, Error>>()?;
+    let game = |stage| Game::from_lines(lines.iter().cloned(), &rules, &game_mode_for(stage))
+        .map_err(|diagnostic| diagnostic.in_file(&path));
+    // Once read as moves, the guide only fails as results on its second column
+    let analysis = OpponentAnalysis::new(&game(Stage::One)?, game(Stage::Two).ok().as_ref(), max_order);
+    print!("{analysis}");
+    Ok(())
+}
 
-    pub fn self_id(&self) -> char {
-        match self {
-            Self::Rock => 'X',
-            Self::Paper => 'Y',
-            Self::Scissors => 'Z',
+fn interactive(rules: Ruleset, mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut bot = None;
+    let mut seed = None;
+    let mut transcript = None;
+    let mut force = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bot" => bot = Some(args.next().ok_or(Error::MissingArgument("a bot policy"))?),
+            "--seed" => {
+                let value = args.next().ok_or(Error::MissingArgument("a seed"))?;
+                seed = Some(value.parse().map_err(|_| Error::InvalidArgument(value))?);
+            }
+            "--transcript" => transcript = Some(args.next().ok_or(Error::MissingArgument("a transcript file name"))?),
+            "--force" => force = true,
+            _ => return Err(Error::InvalidArgument(arg)),
         }
     }
-
-    pub fn inherent_score(&self) -> u32 {
-        match self {
-            Self::Rock => 1,
-            Self::Paper => 2,
-            Self::Scissors => 3,
+    let bot = match Strategy::parse(bot.as_deref().unwrap_or("adaptive"), &rules)? {
+        Strategy::Random if seed.is_none() => Bot::Random(Rng::from_time()),
+        strategy => strategy.bot(&rules, seed.unwrap_or_default()),
+    };
+    // Checked before playing rather than losing the session at the end
+    if transcript.as_deref().is_some_and(|path| !force && Path::new(path).exists()) {
+        return Err(Error::Puzzle("The transcript file already exists, use --force to overwrite it"));
+    }
+
+    let names: Vec<&str> = rules.moves().map(|m| rules.rule(m).name.as_str()).collect();
+    println!("Playing {} against the {bot} bot, enter {} or q to quit", rules.name(), names.join(", "));
+    let mut session = Session::new(&rules, bot);
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            break;
+        };
+        let line = line.trim();
+        if line.eq_ignore_ascii_case("q") {
+            break;
         }
-    }
-
-    pub fn confrontation_result(&self, opponent_move: &Self) -> RoundResult {
-        match self.cmp(opponent_move) {
-            Ordering::Less => RoundResult::Defeat,
-            Ordering::Equal => RoundResult::Draw,
-            Ordering::Greater => RoundResult::Victory
+        let Some(my_move) = rules.find_name(line) else {
+            println!("Unknown move '{line}'");
+            continue;
+        };
+        let turn = session.play(my_move);
+        println!(
+            "Bot plays {}: {}, +{} points, total {}",
+            rules.rule(turn.bot_move).name, turn.result, turn.score, turn.total
+        );
+    }
+
+    match transcript {
+        Some(path) => {
+            let mut file = OpenOptions::new().write(true).create(true).truncate(true).create_new(!force).open(&path)?;
+            file.write_all(session.transcript().as_bytes())?;
+            println!("Final score {}, transcript saved to {path}", session.total());
         }
+        None => println!("Final score {}", session.total()),
     }
+    Ok(())
 }
 
-impl Ord for Move {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        match (self, other) {
-            (Self::Rock, Self::Rock) => Ordering::Equal,
-            (Self::Rock, Self::Paper) => Ordering::Less,
-            (Self::Rock, Self::Scissors) => Ordering::Greater,
-            (Self::Paper, Self::Rock) => Ordering::Greater,
-            (Self::Paper, Self::Paper) => Ordering::Equal,
-            (Self::Paper, Self::Scissors) => Ordering::Less,
-            (Self::Scissors, Self::Rock) => Ordering::Less,
-            (Self::Scissors, Self::Paper) => Ordering::Greater,
-            (Self::Scissors, Self::Scissors) => Ordering::Equal,
+fn tournament(rules: Ruleset, mut args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut strategies = Vec::new();
+    let mut rounds = 1000;
+    let mut seed = 0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--strategy" => {
+                let spec = args.next().ok_or(Error::MissingArgument("a strategy"))?;
+                strategies.push(Strategy::parse(&spec, &rules)?);
+            }
+            "--rounds" => {
+                let value = args.next().ok_or(Error::MissingArgument("a number of rounds"))?;
+                rounds = value.parse().map_err(|_| Error::InvalidArgument(value))?;
+            }
+            "--seed" => {
+                let value = args.next().ok_or(Error::MissingArgument("a seed"))?;
+                seed = value.parse().map_err(|_| Error::InvalidArgument(value))?;
+            }
+            _ => return Err(Error::InvalidArgument(arg)),
         }
     }
-}
-
-impl PartialOrd for Move {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-
-pub struct Round {
-    opponent_move: Move,
-    my_move: Move,
-}
-
-impl Round {
-
-    pub fn new(opponent_move: Move, my_move: Move) -> Round {
-        Round { opponent_move, my_move }
-    }
-
-    pub fn from_line(line: &str, game_mode: &GameMode) -> Option<Round> {
-        let mut move_ids = line.split(' ').map(|s| {
-            s.chars().next()
-        });
-        let Some(opponent_move_id) = move_ids.next()? else { return None };
-        let Some(my_id) = move_ids.next()? else { return None };
-
-        let opponent_move = Move::from(opponent_move_id)?;
-        let my_move = match game_mode {
-            GameMode::Simple => {
-                Move::from(my_id)?
-            }
-            GameMode::ExpectedResult =>
-                Move::for_expected_result(&opponent_move, RoundResult::from(my_id)?),
-        };
-        Some(Round::new(opponent_move, my_move))
+    if strategies.is_empty() {
+        strategies = Strategy::defaults(&rules);
     }
-
-    pub fn score(&self) -> u32 {
-        self.my_move.inherent_score() +
-            self.my_move.confrontation_result(&self.opponent_move).value()
+    let names: HashSet<String> = strategies.iter().map(|strategy| strategy.name(&rules)).collect();
+    if names.len() < 2 {
+        return Err(Error::Puzzle("A tournament needs at least two different strategies"));
     }
-}
-
 
-struct Game {
-    rounds: Vec<Round>,
+    print!("{}", Tournament::run(&rules, &strategies, rounds, seed));
+    Ok(())
 }
 
-impl Game {
-
-    fn from_lines(lines: impl Iterator<Item = String>, game_mode: &GameMode) -> Option<Game> {
-        let mut game = Game { rounds: Vec::new() };
-        for line in lines {
-            let round = Round::from_line(line.as_str(), game_mode)?;
-            game.rounds.push(round);
+fn trace(rules: Ruleset, args: impl Iterator<Item = String>) -> Result<(), Error> {
+    let mut stage = None;
+    let mut path = None;
+    let mut csv = false;
+    for arg in args {
+        match arg.as_str() {
+            "--csv" => csv = true,
+            _ if stage.is_none() => stage = Some(arg.parse::<Stage>()?),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(Error::InvalidArgument(arg)),
         }
-        Some(game)
     }
+    let stage = stage.ok_or(Error::MissingArgument("puzzle stage"))?;
+    let path = path.ok_or(Error::MissingArgument("a file name"))?;
 
-    pub fn total_score(&self) -> u32{
-        self.rounds
-            .iter()
-            .fold(0, |acc, round|
-                acc + round.score()
-            )
+    let game = process_lines(read_lines(&path)?, |lines| {
+        Ok(Game::from_lines(lines, &rules, &game_mode_for(stage)).map_err(|diagnostic| diagnostic.in_file(&path))?)
+    })?;
+    let trace = game.trace();
+    if csv {
+        // Keeps the standard output a single CSV table
+        print!("{}", trace.to_csv());
+        eprint!("{}", trace.summary());
+    } else {
+        print!("{trace}");
     }
-}
-
-
-fn play_game(path: &str, game_mode: &GameMode) -> Result<(), &'static str> {
-    let Ok(lines) = read_lines(path) else {
-        return Err("Unable to read the file")
-    };
-
-    let Some(game) = Game::from_lines(lines, game_mode) else {
-        return Err("Unable to load the game")
-    };
-
-    let total = game.total_score();
-
-    println!("Total score of the game: {total}");
-
     Ok(())
 }
 
-
-fn main() -> Result<(), &'static str> {
-    let stage = std::env::args().nth(1).expect("Expecting puzzle stage");
-    let path = std::env::args().nth(2).expect("Expecting a file name");
-
-    match stage.as_str() {
-        "stage1" => play_game(path.as_str(), &GameMode::Simple),
-        "stage2" => play_game(path.as_str(), &GameMode::ExpectedResult),
-        _ => Err("Unknown stage")
+fn main() -> Result<(), Error> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let rules = rules(&mut args)?;
+
+    // Commands beyond the puzzle stages take their own arguments
+    let command = args.first().cloned();
+    let args = args.into_iter();
+    match command.as_deref() {
+        Some("decrypt") => decrypt(rules, args.skip(1)),
+        Some("analyse") => analyse(rules, args.skip(1)),
+        Some("interactive") => interactive(rules, args.skip(1)),
+        Some("tournament") => tournament(rules, args.skip(1)),
+        Some("trace") => trace(rules, args.skip(1)),
+        _ => run_day_with(2, &rules, Args::from_args(args)?, |_stage, total| format!("Total score of the game: {total}")),
     }
 }
-
-
-fn read_lines<P>(filename: P) -> io::Result<impl Iterator<Item = String>>
-where
-    P: AsRef<Path>
-{
-    let file = File::open(filename)?;
-    let it = io::BufReader::new(file)
-        .lines()
-        .map(|a| a.expect("Bad line"));
-    Ok(it)
-}
\ No newline at end of file
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Game, Move, Round, RoundResult, Ruleset};
+
+
+/// Learns which move an opponent plays after its last `order` moves. Order 0
+/// only counts how often each move is played.
+pub struct OpponentModel {
+    order: usize,
+    moves_count: usize,
+    counts: HashMap<Vec<Move>, Vec<usize>>,
+    history: Vec<Move>,
+}
+
+impl OpponentModel {
+
+    pub fn new(rules: &Ruleset, order: usize) -> OpponentModel {
+        OpponentModel {
+            order,
+            moves_count: rules.moves().count(),
+            counts: HashMap::new(),
+            history: Vec::new(),
+        }
+    }
+
+    fn context(&self) -> Option<&[Move]> {
+        let start = self.history.len().checked_sub(self.order)?;
+        Some(&self.history[start..])
+    }
+
+    /// The move most often played in the current context, the first one in
+    /// rules order on ties, or `None` when the context was never seen.
+    pub fn predict(&self) -> Option<Move> {
+        let counts = self.counts.get(self.context()?)?;
+        let best = (0..counts.len()).rev().max_by_key(|index| counts[*index])?;
+        Some(Move::from_index(best))
+    }
+
+    pub fn observe(&mut self, m: Move) {
+        if let Some(context) = self.context() {
+            let counts = self.counts.entry(context.to_vec()).or_insert_with(|| vec![0; self.moves_count]);
+            counts[m.index()] += 1;
+        }
+        self.history.push(m);
+    }
+}
+
+
+/// How a model of the opponent would have fared, predicting each round from
+/// the previous ones only.
+pub struct ModelReport {
+    pub order: usize,
+    pub predicted: usize,
+    pub hits: usize,
+    pub counter_score: u32,
+}
+
+pub struct OpponentAnalysis {
+    rules: Ruleset,
+    pub rounds: usize,
+    pub frequencies: Vec<usize>,
+    pub models: Vec<ModelReport>,
+    pub simple_score: u32,
+    /// `None` when the second column of the guide is not made of round results.
+    pub expected_result_score: Option<u32>,
+}
+
+/// Shannon entropy, in bits, of the distribution given by `counts`.
+fn entropy(counts: &[usize]) -> f64 {
+    let total: usize = counts.iter().sum();
+    counts.iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+impl OpponentAnalysis {
+
+    /// Fits models of order 0 to `max_order` to the opponent column of the
+    /// guide read as moves (`simple`), and compares its score with the guide
+    /// read as results (`expected_result`) when it can be.
+    pub fn new(simple: &Game, expected_result: Option<&Game>, max_order: usize) -> OpponentAnalysis {
+        let rules = simple.rules();
+        let moves: Vec<Move> = simple.rounds().iter().map(Round::opponent_move).collect();
+
+        let mut frequencies = vec![0; rules.moves().count()];
+        for m in &moves {
+            frequencies[m.index()] += 1;
+        }
+
+        let models = (0..=max_order)
+            .map(|order| {
+                let mut model = OpponentModel::new(rules, order);
+                let mut report = ModelReport { order, predicted: 0, hits: 0, counter_score: 0 };
+                for m in &moves {
+                    let prediction = model.predict();
+                    if prediction.is_some() {
+                        report.predicted += 1;
+                    }
+                    if prediction == Some(*m) {
+                        report.hits += 1;
+                    }
+                    let guess = prediction.unwrap_or(Move::from_index(0));
+                    let counter = rules.for_expected_result(guess, RoundResult::Victory);
+                    report.counter_score += Round::new(*m, counter).score(rules);
+                    model.observe(*m);
+                }
+                report
+            })
+            .collect();
+
+        OpponentAnalysis {
+            rules: rules.clone(),
+            rounds: moves.len(),
+            frequencies,
+            models,
+            simple_score: simple.total_score(),
+            expected_result_score: expected_result.map(Game::total_score),
+        }
+    }
+
+    pub fn entropy(&self) -> f64 {
+        entropy(&self.frequencies)
+    }
+
+    /// Entropy of a uniformly random opponent.
+    pub fn max_entropy(&self) -> f64 {
+        (self.frequencies.len() as f64).log2()
+    }
+}
+
+impl fmt::Display for OpponentAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let percent = |count: usize, total: usize| 100.0 * count as f64 / total.max(1) as f64;
+        writeln!(f, "Opponent moves over {} rounds:", self.rounds)?;
+        for m in self.rules.moves() {
+            let count = self.frequencies[m.index()];
+            writeln!(f, "  {:<10} {count:>6} ({:.1}%)", self.rules.rule(m).name, percent(count, self.rounds))?;
+        }
+        writeln!(f, "Entropy: {:.3} bits out of {:.3}", self.entropy(), self.max_entropy())?;
+        writeln!(f)?;
+
+        writeln!(f, "Model              hits    rate  counter score")?;
+        for model in &self.models {
+            let name = match model.order {
+                0 => String::from("frequency"),
+                order => format!("markov order {order}"),
+            };
+            writeln!(
+                f, "{name:<16} {:>6} {:>6.1}%  {:>13}",
+                model.hits, percent(model.hits, self.rounds), model.counter_score
+            )?;
+        }
+        writeln!(f, "{:<16} {:>6} {:>6.1}%", "chance", "", 100.0 / self.frequencies.len() as f64)?;
+        writeln!(f)?;
+
+        writeln!(f, "Guide read as moves:   {}", self.simple_score)?;
+        match self.expected_result_score {
+            Some(score) => writeln!(f, "Guide read as results: {score}"),
+            None => writeln!(f, "Guide read as results: unavailable, the second column is not made of results"),
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use aoc_common::Error;
+
+use crate::{RoundResult, Scoring};
+
+
+/// A move of a [`Ruleset`], standing for its position in the rules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Move(usize);
+
+impl Move {
+
+    pub(crate) fn from_index(index: usize) -> Move {
+        Move(index)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+
+#[derive(Clone, Debug)]
+pub struct MoveRule {
+    pub name: String,
+    pub score: u32,
+    pub opponent_id: char,
+    pub self_id: char,
+}
+
+impl MoveRule {
+
+    pub fn new(name: &str, score: u32, opponent_id: char, self_id: char) -> MoveRule {
+        MoveRule { name: String::from(name), score, opponent_id, self_id }
+    }
+}
+
+
+#[derive(Debug)]
+pub enum RulesError {
+    InvalidToml(String),
+    MissingField { field: &'static str, index: usize },
+    UnknownMove(String),
+    DuplicateMove(String),
+    DuplicateId(char),
+    MutualVictory(String, String),
+    Unbeatable(String),
+    Unbeating(String),
+    UnknownRuleset(String),
+    InvalidPoints(String),
+    TooFewMoves(usize),
+    InvalidBeats { rows: usize, moves: usize },
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidToml(message) => write!(f, "Invalid rules file: {message}"),
+            Self::MissingField { field, index } => write!(f, "Move #{} has no valid '{field}'", index + 1),
+            Self::UnknownMove(name) => write!(f, "Unknown move '{name}' in the rules"),
+            Self::DuplicateMove(name) => write!(f, "Move '{name}' is defined twice"),
+            Self::DuplicateId(id) => write!(f, "Letter code '{id}' is used by several moves"),
+            Self::MutualVictory(lhs, rhs) => write!(f, "'{lhs}' and '{rhs}' both beat each other"),
+            Self::Unbeatable(name) => write!(f, "No move beats '{name}'"),
+            Self::Unbeating(name) => write!(f, "'{name}' beats no move"),
+            Self::UnknownRuleset(name) => write!(f, "Unknown ruleset '{name}', expecting rps, rpsls or a rules file"),
+            Self::InvalidPoints(points) => write!(f, "Invalid points '{points}'"),
+            Self::TooFewMoves(count) => write!(f, "The rules have {count} moves, expecting at least 2"),
+            Self::InvalidBeats { rows, moves } => write!(f, "Expecting who beats whom between {moves} moves, got {rows} rows"),
+        }
+    }
+}
+
+impl std::error::Error for RulesError {}
+
+
+/// The moves of a cyclic game, with who beats whom, their scores and the
+/// letters coding them in a strategy guide.
+#[derive(Clone, Debug)]
+pub struct Ruleset {
+    name: String,
+    moves: Vec<MoveRule>,
+    beats: Vec<Vec<bool>>,
+    outcome_points: [u32; 3],
+}
+
+impl Ruleset {
+
+    /// Checks that no pair of moves beat each other, and that every move both
+    /// beats and is beaten by another one so that any round result can be played.
+    /// `beats[lhs][rhs]` tells whether move `lhs` beats move `rhs`.
+    pub fn new(name: &str, moves: Vec<MoveRule>, beats: Vec<Vec<bool>>) -> Result<Ruleset, RulesError> {
+        if moves.len() < 2 {
+            return Err(RulesError::TooFewMoves(moves.len()));
+        }
+        if beats.len() != moves.len() || beats.iter().any(|wins| wins.len() != moves.len()) {
+            return Err(RulesError::InvalidBeats { rows: beats.len(), moves: moves.len() });
+        }
+        for (index, rule) in moves.iter().enumerate() {
+            if moves[..index].iter().any(|other| other.name == rule.name) {
+                return Err(RulesError::DuplicateMove(rule.name.clone()));
+            }
+            let ids = moves[..index].iter().flat_map(|other| [other.opponent_id, other.self_id]);
+            if let Some(id) = ids.into_iter().find(|id| *id == rule.opponent_id || *id == rule.self_id) {
+                return Err(RulesError::DuplicateId(id));
+            }
+        }
+        for (lhs, wins) in beats.iter().enumerate() {
+            if let Some(rhs) = (0..=lhs).find(|rhs| wins[*rhs] && beats[*rhs][lhs]) {
+                return Err(RulesError::MutualVictory(moves[lhs].name.clone(), moves[rhs].name.clone()));
+            }
+            if !wins.iter().enumerate().any(|(rhs, wins)| *wins && rhs != lhs) {
+                return Err(RulesError::Unbeating(moves[lhs].name.clone()));
+            }
+            if !beats.iter().enumerate().any(|(rhs, wins)| wins[lhs] && rhs != lhs) {
+                return Err(RulesError::Unbeatable(moves[lhs].name.clone()));
+            }
+        }
+        let outcome_points = RoundResult::all().map(|result| result.value());
+        Ok(Ruleset { name: String::from(name), moves, beats, outcome_points })
+    }
+
+    /// A game where moves are listed in cycle order, each beating the half of
+    /// the other moves preceding it. With an even number of moves, opposite
+    /// moves would beat each other and the rules are rejected.
+    pub fn cyclic(name: &str, moves: Vec<MoveRule>) -> Result<Ruleset, RulesError> {
+        let count = moves.len();
+        let beats = (0..count)
+            .map(|lhs| (0..count).map(|rhs| (1..=count / 2).contains(&((lhs + count - rhs) % count))).collect())
+            .collect();
+        Ruleset::new(name, moves, beats)
+    }
+
+    pub fn rock_paper_scissors() -> Ruleset {
+        Ruleset::cyclic("rock-paper-scissors", vec![
+            MoveRule::new("Rock", 1, 'A', 'X'),
+            MoveRule::new("Paper", 2, 'B', 'Y'),
+            MoveRule::new("Scissors", 3, 'C', 'Z'),
+        ]).expect("Built-in rules are consistent")
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Ruleset {
+        Ruleset::cyclic("rock-paper-scissors-lizard-spock", vec![
+            MoveRule::new("Rock", 1, 'A', 'V'),
+            MoveRule::new("Spock", 5, 'E', 'Z'),
+            MoveRule::new("Paper", 2, 'B', 'W'),
+            MoveRule::new("Lizard", 4, 'D', 'Y'),
+            MoveRule::new("Scissors", 3, 'C', 'X'),
+        ]).expect("Built-in rules are consistent")
+    }
+
+    /// A built-in ruleset (`rps` or `rpsls`), or else a rules file.
+    pub fn named(name: &str) -> Result<Ruleset, Error> {
+        match name {
+            "rps" => Ok(Self::rock_paper_scissors()),
+            "rpsls" => Ok(Self::rock_paper_scissors_lizard_spock()),
+            path if Path::new(path).is_file() => Self::load(path),
+            _ => Err(Error::Solver(Box::new(RulesError::UnknownRuleset(String::from(name))))),
+        }
+    }
+
+    pub fn load<P>(path: P) -> Result<Ruleset, Error>
+    where
+        P: AsRef<Path>
+    {
+        let content = fs::read_to_string(path)?;
+        Self::from_toml(&content).map_err(|error| Error::Solver(Box::new(error)))
+    }
+
+    /// Reads rules such as:
+    ///
+    /// ```toml
+    /// name = "rock-paper-scissors"
+    ///
+    /// [[moves]]
+    /// name = "Rock"
+    /// score = 1
+    /// opponent = "A"
+    /// self = "X"
+    /// beats = ["Scissors"]
+    /// ```
+    pub fn from_toml(content: &str) -> Result<Ruleset, RulesError> {
+        let table: toml::Table = content.parse()
+            .map_err(|error: toml::de::Error| RulesError::InvalidToml(error.message().to_string()))?;
+        let name = table.get("name").and_then(toml::Value::as_str).unwrap_or("custom");
+        let entries = table.get("moves").and_then(toml::Value::as_array).map(Vec::as_slice).unwrap_or_default();
+
+        let mut moves = Vec::new();
+        for (index, entry) in entries.iter().enumerate() {
+            let get = |field| entry.get(field).ok_or(RulesError::MissingField { field, index });
+            let text = |field| get(field)?.as_str().ok_or(RulesError::MissingField { field, index });
+            let letter = |field| {
+                let mut letters = text(field)?.chars();
+                match (letters.next(), letters.next()) {
+                    (Some(letter), None) => Ok(letter),
+                    _ => Err(RulesError::MissingField { field, index }),
+                }
+            };
+            let score = get("score")?.as_integer()
+                .and_then(|score| u32::try_from(score).ok())
+                .ok_or(RulesError::MissingField { field: "score", index })?;
+            moves.push(MoveRule::new(text("name")?, score, letter("opponent")?, letter("self")?));
+        }
+
+        let mut beats = vec![vec![false; moves.len()]; moves.len()];
+        for (index, entry) in entries.iter().enumerate() {
+            let beaten = entry.get("beats").and_then(toml::Value::as_array)
+                .ok_or(RulesError::MissingField { field: "beats", index })?;
+            for name in beaten {
+                let name = name.as_str().ok_or(RulesError::MissingField { field: "beats", index })?;
+                let rhs = moves.iter().position(|rule| rule.name == name)
+                    .ok_or_else(|| RulesError::UnknownMove(String::from(name)))?;
+                beats[index][rhs] = true;
+            }
+        }
+        // Moves carry their own score, only the points of the results may be changed
+        let mut outcomes = toml::Table::new();
+        if let Some(points) = table.get("outcomes") {
+            outcomes.insert(String::from("outcomes"), points.clone());
+        }
+        Ruleset::new(name, moves, beats)?.with_scoring(&Scoring::from_table(&outcomes)?)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        (0..self.moves.len()).map(Move)
+    }
+
+    pub fn rule(&self, m: Move) -> &MoveRule {
+        &self.moves[m.0]
+    }
+
+    /// The move coded by `id` in either column of a strategy guide.
+    pub fn find(&self, id: char) -> Option<Move> {
+        self.moves.iter().position(|rule| rule.self_id == id || rule.opponent_id == id).map(Move)
+    }
+
+    /// The move named `name`, ignoring case, or else coded by the letter `name`.
+    pub fn find_name(&self, name: &str) -> Option<Move> {
+        let position = self.moves.iter().position(|rule| rule.name.eq_ignore_ascii_case(name));
+        let mut letters = name.chars();
+        match (position, letters.next(), letters.next()) {
+            (Some(position), _, _) => Some(Move(position)),
+            (None, Some(letter), None) => self.find(letter.to_ascii_uppercase()),
+            _ => None,
+        }
+    }
+
+    pub fn find_opponent(&self, id: char) -> Option<Move> {
+        self.moves.iter().position(|rule| rule.opponent_id == id).map(Move)
+    }
+
+    pub fn find_self(&self, id: char) -> Option<Move> {
+        self.moves.iter().position(|rule| rule.self_id == id).map(Move)
+    }
+
+    pub fn beats(&self, m: Move, other: Move) -> bool {
+        self.beats[m.0][other.0]
+    }
+
+    pub fn inherent_score(&self, m: Move) -> u32 {
+        self.moves[m.0].score
+    }
+
+    pub(crate) fn set_inherent_score(&mut self, m: Move, points: u32) {
+        self.moves[m.0].score = points;
+    }
+
+    /// Points of a round result, [`RoundResult::value`] unless scored otherwise.
+    pub fn outcome_points(&self, result: RoundResult) -> u32 {
+        self.outcome_points[result as usize]
+    }
+
+    pub(crate) fn set_outcome_points(&mut self, result: RoundResult, points: u32) {
+        self.outcome_points[result as usize] = points;
+    }
+
+    pub fn confrontation_result(&self, m: Move, opponent_move: Move) -> RoundResult {
+        if self.beats(m, opponent_move) {
+            RoundResult::Victory
+        } else if self.beats(opponent_move, m) {
+            RoundResult::Defeat
+        } else {
+            RoundResult::Draw
+        }
+    }
+
+    /// The first move, in rules order, giving `expected_result` against `opponent_move`.
+    pub fn for_expected_result(&self, opponent_move: Move, expected_result: RoundResult) -> Move {
+        match expected_result {
+            RoundResult::Draw => opponent_move,
+            expected_result => self.moves()
+                .find(|m| self.confrontation_result(*m, opponent_move) == expected_result)
+                .expect("Rulesets let every move be beaten and beat another one"),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::Path;
+
+use aoc_common::Error;
+
+use crate::{RoundResult, RulesError, Ruleset};
+
+
+/// Points overriding those of a [`Ruleset`], for each round result or move.
+#[derive(Clone, Debug, Default)]
+pub struct Scoring {
+    pub outcomes: Vec<(RoundResult, u32)>,
+    pub moves: Vec<(String, u32)>,
+}
+
+impl Scoring {
+
+    pub fn load<P>(path: P) -> Result<Scoring, Error>
+    where
+        P: AsRef<Path>
+    {
+        let content = fs::read_to_string(path)?;
+        let table: toml::Table = content.parse().map_err(|error: toml::de::Error| {
+            Error::Solver(Box::new(RulesError::InvalidToml(error.message().to_string())))
+        })?;
+        Self::from_table(&table).map_err(|error| Error::Solver(Box::new(error)))
+    }
+
+    /// Reads the optional `[outcomes]` and `[moves]` tables:
+    ///
+    /// ```toml
+    /// [outcomes]
+    /// defeat = 0
+    /// draw = 1
+    /// victory = 2
+    ///
+    /// [moves]
+    /// Rock = 3
+    /// ```
+    pub fn from_table(table: &toml::Table) -> Result<Scoring, RulesError> {
+        let points = |section: &'static str| -> Result<Vec<(String, u32)>, RulesError> {
+            let Some(entries) = table.get(section) else {
+                return Ok(Vec::new());
+            };
+            let entries = entries.as_table().ok_or(RulesError::InvalidPoints(String::from(section)))?;
+            entries.iter()
+                .map(|(name, value)| {
+                    let points = value.as_integer()
+                        .and_then(|points| u32::try_from(points).ok())
+                        .ok_or_else(|| RulesError::InvalidPoints(name.clone()))?;
+                    Ok((name.clone(), points))
+                })
+                .collect()
+        };
+
+        let outcomes = points("outcomes")?.into_iter()
+            .map(|(name, points)| {
+                let result = name.parse().map_err(|_| RulesError::InvalidPoints(name))?;
+                Ok((result, points))
+            })
+            .collect::<Result<_, RulesError>>()?;
+        Ok(Scoring { outcomes, moves: points("moves")? })
+    }
+
+    /// Reads `name=points` pairs separated by commas, naming round results
+    /// (`defeat`, `draw`, `victory`) or moves, such as `draw=1,Rock=5`.
+    pub fn from_points(points: &str) -> Result<Scoring, RulesError> {
+        let mut scoring = Scoring::default();
+        for pair in points.split(',') {
+            let invalid = || RulesError::InvalidPoints(String::from(pair));
+            let (name, value) = pair.split_once('=').ok_or_else(invalid)?;
+            let value = value.trim().parse().map_err(|_| invalid())?;
+            match name.trim().parse() {
+                Ok(result) => scoring.outcomes.push((result, value)),
+                Err(_error) => scoring.moves.push((String::from(name.trim()), value)),
+            }
+        }
+        Ok(scoring)
+    }
+
+    /// `other` takes precedence over `self` for the points both set.
+    pub fn merged(mut self, other: Scoring) -> Scoring {
+        self.outcomes.extend(other.outcomes);
+        self.moves.extend(other.moves);
+        self
+    }
+}
+
+impl Ruleset {
+
+    pub fn with_scoring(mut self, scoring: &Scoring) -> Result<Ruleset, RulesError> {
+        for (result, points) in &scoring.outcomes {
+            self.set_outcome_points(*result, *points);
+        }
+        for (name, points) in &scoring.moves {
+            let m = self.moves()
+                .find(|m| self.rule(*m).name.eq_ignore_ascii_case(name))
+                .ok_or_else(|| RulesError::UnknownMove(name.clone()))?;
+            self.set_inherent_score(m, *points);
+        }
+        Ok(self)
+    }
+}
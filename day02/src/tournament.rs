@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::path::Path;
+
+use aoc_common::{process_lines, read_lines, Error};
+
+use crate::{Bot, Game, GameMode, Move, Round, RoundResult, Ruleset, Rng};
+
+
+/// How to build a fresh [`Bot`] for every match.
+#[derive(Clone)]
+pub enum Strategy {
+    Constant(Move),
+    Cyclic,
+    Random,
+    WinStayLoseShift,
+    FrequencyCounter,
+    Replay { name: String, rounds: Vec<Round> },
+}
+
+impl Strategy {
+
+    /// Every strategy needing no guide, the constant one playing the first move.
+    pub fn defaults(rules: &Ruleset) -> Vec<Strategy> {
+        let first = rules.moves().next().expect("Rulesets have moves");
+        vec![
+            Self::Constant(first),
+            Self::Cyclic,
+            Self::Random,
+            Self::WinStayLoseShift,
+            Self::FrequencyCounter,
+        ]
+    }
+
+    /// Reads `constant:<move>`, `cyclic`, `random`, `wsls`, `frequency` or
+    /// `replay:<guide>`, where the name of an existing guide file also stands
+    /// for a replay.
+    pub fn parse(spec: &str, rules: &Ruleset) -> Result<Strategy, Error> {
+        match spec.split_once(':') {
+            Some(("constant", name)) => {
+                let m = rules.find_name(name).ok_or_else(|| Error::InvalidArgument(String::from(spec)))?;
+                Ok(Self::Constant(m))
+            }
+            Some(("replay", path)) => Self::replay(path, rules),
+            _ => match spec {
+                "cyclic" => Ok(Self::Cyclic),
+                "random" => Ok(Self::Random),
+                "wsls" => Ok(Self::WinStayLoseShift),
+                "frequency" | "adaptive" => Ok(Self::FrequencyCounter),
+                path if Path::new(path).is_file() => Self::replay(path, rules),
+                _ => Err(Error::InvalidArgument(format!(
+                    "{spec}, expecting constant:<move>, cyclic, random, wsls, frequency or replay:<guide>"
+                ))),
+            },
+        }
+    }
+
+    fn replay(path: &str, rules: &Ruleset) -> Result<Strategy, Error> {
+        let game = process_lines(read_lines(path)?, |lines| {
+            Ok(Game::from_lines(lines, rules, &GameMode::Simple).map_err(|diagnostic| diagnostic.in_file(path))?)
+        })?;
+        if game.rounds().is_empty() {
+            return Err(Error::Puzzle("The guide to replay has no rounds"));
+        }
+        Ok(Self::Replay { name: String::from(path), rounds: game.rounds().to_vec() })
+    }
+
+    pub fn bot(&self, rules: &Ruleset, seed: u64) -> Bot {
+        match self {
+            Self::Constant(m) => Bot::Constant(*m),
+            Self::Cyclic => Bot::Cyclic { position: 0 },
+            Self::Random => Bot::random(seed),
+            Self::WinStayLoseShift => Bot::WinStayLoseShift { last: None },
+            Self::FrequencyCounter => Bot::adaptive(rules),
+            Self::Replay { rounds, .. } => Bot::replay(rounds),
+        }
+    }
+
+    pub fn name(&self, rules: &Ruleset) -> String {
+        match self {
+            Self::Constant(m) => format!("constant {}", rules.rule(*m).name),
+            Self::Cyclic => String::from("cyclic"),
+            Self::Random => String::from("random"),
+            Self::WinStayLoseShift => String::from("win-stay/lose-shift"),
+            Self::FrequencyCounter => String::from("frequency counter"),
+            Self::Replay { name, .. } => format!("replay {name}"),
+        }
+    }
+}
+
+
+#[derive(Default)]
+pub struct Standing {
+    pub name: String,
+    pub points: u64,
+    pub match_wins: usize,
+    pub match_draws: usize,
+    pub match_losses: usize,
+    pub round_wins: usize,
+    pub round_draws: usize,
+    pub round_losses: usize,
+}
+
+impl Standing {
+
+    fn record(&mut self, result: RoundResult) {
+        match result {
+            RoundResult::Victory => self.round_wins += 1,
+            RoundResult::Draw => self.round_draws += 1,
+            RoundResult::Defeat => self.round_losses += 1,
+        }
+    }
+}
+
+/// A round-robin where every pair of strategies meets once over the same
+/// number of rounds, scored with [`Round::score`]. Random bots are seeded from
+/// the tournament seed so that a seed always gives the same standings.
+pub struct Tournament {
+    pub rounds: usize,
+    pub seed: u64,
+    pub standings: Vec<Standing>,
+}
+
+impl Tournament {
+
+    pub fn run(rules: &Ruleset, strategies: &[Strategy], rounds: usize, seed: u64) -> Tournament {
+        let mut standings: Vec<Standing> = strategies.iter()
+            .map(|strategy| Standing { name: strategy.name(rules), ..Standing::default() })
+            .collect();
+        let mut seeds = Rng::new(seed);
+
+        for first in 0..strategies.len() {
+            for second in first + 1..strategies.len() {
+                let mut bots = [
+                    strategies[first].bot(rules, seeds.next_u64()),
+                    strategies[second].bot(rules, seeds.next_u64()),
+                ];
+                let mut points = [0, 0];
+                for _ in 0..rounds {
+                    let moves = [bots[0].play(rules), bots[1].play(rules)];
+                    bots[0].observe(moves[0], moves[1]);
+                    bots[1].observe(moves[1], moves[0]);
+                    for (side, index) in [(0, first), (1, second)] {
+                        let round = Round::new(moves[1 - side], moves[side]);
+                        points[side] += u64::from(round.score(rules));
+                        standings[index].record(rules.confrontation_result(moves[side], moves[1 - side]));
+                    }
+                }
+
+                standings[first].points += points[0];
+                standings[second].points += points[1];
+                match points[0].cmp(&points[1]) {
+                    Ordering::Greater => {
+                        standings[first].match_wins += 1;
+                        standings[second].match_losses += 1;
+                    }
+                    Ordering::Less => {
+                        standings[first].match_losses += 1;
+                        standings[second].match_wins += 1;
+                    }
+                    Ordering::Equal => {
+                        standings[first].match_draws += 1;
+                        standings[second].match_draws += 1;
+                    }
+                }
+            }
+        }
+
+        standings.sort_by(|lhs, rhs| rhs.points.cmp(&lhs.points).then_with(|| lhs.name.cmp(&rhs.name)));
+        Tournament { rounds, seed, standings }
+    }
+}
+
+impl fmt::Display for Tournament {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Round-robin over {} rounds per match, seed {}", self.rounds, self.seed)?;
+        writeln!(f, "#   {:<24} {:>10} {:>4} {:>4} {:>4} {:>8} {:>8} {:>8}", "strategy", "points", "W", "D", "L", "won", "drawn", "lost")?;
+        for (rank, standing) in self.standings.iter().enumerate() {
+            writeln!(
+                f, "{:<3} {:<24} {:>10} {:>4} {:>4} {:>4} {:>8} {:>8} {:>8}",
+                rank + 1, standing.name, standing.points,
+                standing.match_wins, standing.match_draws, standing.match_losses,
+                standing.round_wins, standing.round_draws, standing.round_losses,
+            )?;
+        }
+        Ok(())
+    }
+}
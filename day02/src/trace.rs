@@ -0,0 +1,141 @@
+use std::fmt;
+
+use crate::{Game, Move, RoundResult, Ruleset};
+
+
+/// The details of a round's score.
+pub struct RoundTrace {
+    pub opponent_move: Move,
+    pub my_move: Move,
+    pub result: RoundResult,
+    pub move_points: u32,
+    pub result_points: u32,
+}
+
+impl RoundTrace {
+
+    pub fn score(&self) -> u32 {
+        self.move_points + self.result_points
+    }
+}
+
+/// Round by round breakdown of a [`Game`], ending with a summary.
+pub struct Trace {
+    rules: Ruleset,
+    pub rounds: Vec<RoundTrace>,
+}
+
+/// Rounds played and points won with one of my moves.
+pub struct MovePoints {
+    pub m: Move,
+    pub rounds: usize,
+    pub points: u32,
+}
+
+impl Game {
+
+    pub fn trace(&self) -> Trace {
+        let rules = self.rules();
+        let rounds = self.rounds().iter()
+            .map(|round| {
+                let result = rules.confrontation_result(round.my_move(), round.opponent_move());
+                RoundTrace {
+                    opponent_move: round.opponent_move(),
+                    my_move: round.my_move(),
+                    result,
+                    move_points: rules.inherent_score(round.my_move()),
+                    result_points: rules.outcome_points(result),
+                }
+            })
+            .collect();
+        Trace { rules: rules.clone(), rounds }
+    }
+}
+
+impl Trace {
+
+    pub fn count(&self, result: RoundResult) -> usize {
+        self.rounds.iter().filter(|round| round.result == result).count()
+    }
+
+    pub fn total_score(&self) -> u32 {
+        self.rounds.iter().map(RoundTrace::score).sum()
+    }
+
+    pub fn points_by_move(&self) -> Vec<MovePoints> {
+        self.rules.moves()
+            .map(|m| {
+                let rounds = self.rounds.iter().filter(|round| round.my_move == m);
+                MovePoints {
+                    m,
+                    rounds: rounds.clone().count(),
+                    points: rounds.map(RoundTrace::score).sum(),
+                }
+            })
+            .collect()
+    }
+
+    /// The most rounds in a row ending with `result`.
+    pub fn longest_streak(&self, result: RoundResult) -> usize {
+        let mut longest = 0;
+        let mut current = 0;
+        for round in &self.rounds {
+            current = if round.result == result { current + 1 } else { 0 };
+            longest = longest.max(current);
+        }
+        longest
+    }
+
+    /// One row per round, the summary being left to [`Trace::summary`].
+    pub fn to_csv(&self) -> String {
+        let name = |m| self.rules.rule(m).name.as_str();
+        let mut csv = String::from("round,opponent,me,result,move_points,result_points,score\n");
+        for (index, round) in self.rounds.iter().enumerate() {
+            csv += &format!(
+                "{},{},{},{},{},{},{}\n",
+                index + 1, name(round.opponent_move), name(round.my_move), round.result,
+                round.move_points, round.result_points, round.score()
+            );
+        }
+        csv
+    }
+
+    pub fn summary(&self) -> TraceSummary<'_> {
+        TraceSummary(self)
+    }
+}
+
+/// The results, points by move and streaks of a [`Trace`], without its rounds.
+pub struct TraceSummary<'a>(&'a Trace);
+
+impl fmt::Display for TraceSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let trace = self.0;
+        writeln!(
+            f, "{} victories, {} draws, {} defeats",
+            trace.count(RoundResult::Victory), trace.count(RoundResult::Draw), trace.count(RoundResult::Defeat)
+        )?;
+        for points in trace.points_by_move() {
+            writeln!(f, "{:<10} {:>6} rounds {:>8} points", trace.rules.rule(points.m).name, points.rounds, points.points)?;
+        }
+        writeln!(f, "Longest winning streak: {}", trace.longest_streak(RoundResult::Victory))?;
+        writeln!(f, "Longest losing streak:  {}", trace.longest_streak(RoundResult::Defeat))?;
+        writeln!(f, "Total score: {}", trace.total_score())
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |m| self.rules.rule(m).name.as_str();
+        writeln!(f, "{:>6}  {:<10} {:<10} {:<8} {:>5} {:>7} {:>6}", "round", "opponent", "me", "result", "move", "result", "score")?;
+        for (index, round) in self.rounds.iter().enumerate() {
+            writeln!(
+                f, "{:>6}  {:<10} {:<10} {:<8} {:>5} {:>7} {:>6}",
+                index + 1, name(round.opponent_move), name(round.my_move), round.result.to_string(),
+                round.move_points, round.result_points, round.score()
+            )?;
+        }
+        writeln!(f)?;
+        write!(f, "{}", self.summary())
+    }
+}
@@ -0,0 +1,149 @@
+use aoc_common::{lines_from_str, Answer, Solver, Stage};
+
+use day02::{Bot, EncryptedGuide, Game, GameMode, MoveRule, OpponentAnalysis, Puzzle, RoundResult, Ruleset, Scoring, Session, Strategy, Tournament};
+
+
+const GUIDE: &str = "A Y\nB X\nC Z";
+
+fn game(guide: &str, mode: GameMode) -> Game {
+    Game::from_lines(lines_from_str(guide), &Ruleset::rock_paper_scissors(), &mode)
+        .expect("Unable to read the guide")
+}
+
+
+#[test]
+fn rules_file_matches_builtin() {
+    let guide = "A V\nE X\nD W\nC Z\nB Y\nE Z";
+    let rules = Ruleset::load(concat!(env!("CARGO_MANIFEST_DIR"), "/rpsls.toml"))
+        .expect("Unable to load the rules");
+    let builtin = Ruleset::rock_paper_scissors_lizard_spock();
+    assert_eq!(rules.solve_str(Stage::One, guide).expect("Unable to solve"), Answer::from(38u32));
+    assert_eq!(builtin.solve_str(Stage::One, guide).expect("Unable to solve"), Answer::from(38u32));
+    for guide in [guide, "A X\nB Y", "C V\nD W\nE X\nA Y\nB Z"] {
+        assert_eq!(rules.solve_str(Stage::Two, guide).ok(), builtin.solve_str(Stage::Two, guide).ok());
+    }
+
+    let rps = Ruleset::rock_paper_scissors();
+    for stage in [Stage::One, Stage::Two] {
+        assert_eq!(rps.solve_str(stage, GUIDE).ok(), Puzzle.solve_str(stage, GUIDE).ok());
+    }
+}
+
+#[test]
+fn rules_need_moves_beating_each_other() {
+    let error = Ruleset::from_toml("name = \"empty\"").err().map(|error| error.to_string());
+    assert_eq!(error.as_deref(), Some("The rules have 0 moves, expecting at least 2"));
+
+    let rock = MoveRule::new("Rock", 1, 'A', 'X');
+    let paper = MoveRule::new("Paper", 2, 'B', 'Y');
+    assert!(Ruleset::new("rock", vec![rock.clone()], vec![vec![false]]).is_err());
+    assert!(Ruleset::new("short", vec![rock.clone(), paper.clone()], vec![vec![false, true]]).is_err());
+
+    let scissors = MoveRule::new("Scissors", 3, 'C', 'Z');
+    let well = MoveRule::new("Well", 4, 'D', 'W');
+    assert!(Ruleset::cyclic("odd", vec![rock.clone(), paper.clone(), scissors.clone()]).is_ok());
+    assert!(Ruleset::cyclic("even", vec![rock, paper, scissors, well]).is_err());
+}
+
+#[test]
+fn decryption_includes_both_stages() {
+    let guide = EncryptedGuide::from_lines(lines_from_str(GUIDE), &Ruleset::rock_paper_scissors())
+        .expect("Unable to read the guide");
+    let decryption = guide.decrypt();
+    assert_eq!(decryption.hypotheses.len(), 12);
+    let score_of = |stage| decryption.hypotheses.iter()
+        .find(|hypothesis| hypothesis.stage == Some(stage))
+        .map(|hypothesis| hypothesis.total_score);
+    assert_eq!(score_of(Stage::One), Some(15));
+    assert_eq!(score_of(Stage::Two), Some(12));
+    assert!(decryption.hypotheses.windows(2).all(|pair| pair[0].total_score >= pair[1].total_score));
+    assert!(guide.decrypt().matching(12).hypotheses.iter().all(|hypothesis| hypothesis.total_score == 12));
+}
+
+#[test]
+fn opponent_model_counters_a_repetitive_opponent() {
+    let guide = "A X\nB X\nA X\nB X\nA X\nB X\nA X\nB X";
+    let analysis = OpponentAnalysis::new(&game(guide, GameMode::Simple), Some(&game(guide, GameMode::ExpectedResult)), 1);
+
+    assert_eq!(analysis.frequencies, [4, 4, 0]);
+    assert!((analysis.entropy() - 1.0).abs() < 1e-9);
+    let markov = &analysis.models[1];
+    assert_eq!((markov.predicted, markov.hits), (5, 5));
+    assert!(markov.counter_score > analysis.models[0].counter_score);
+    assert_eq!(analysis.simple_score, game(guide, GameMode::Simple).total_score());
+    assert_eq!(analysis.expected_result_score, Some(game(guide, GameMode::ExpectedResult).total_score()));
+
+    let rules = Ruleset::rock_paper_scissors_lizard_spock();
+    let rpsls = Game::from_lines(lines_from_str("A V\nB W\nC X"), &rules, &GameMode::Simple)
+        .expect("Unable to read the guide");
+    let analysis = OpponentAnalysis::new(&rpsls, None, 1);
+    assert_eq!(analysis.frequencies, [1, 0, 1, 0, 1]);
+    assert_eq!(analysis.expected_result_score, None);
+}
+
+#[test]
+fn session_transcript_replays_to_the_same_score() {
+    let rules = Ruleset::rock_paper_scissors();
+    let mut session = Session::new(&rules, Bot::replay(game(GUIDE, GameMode::Simple).rounds()));
+    for name in ["paper", "rock", "Z", "scissors"] {
+        session.play(rules.find_name(name).expect("Unknown move"));
+    }
+    assert_eq!(session.transcript(), "A Y\nB X\nC Z\nA Z\n");
+    let replayed = Puzzle.solve_str(Stage::One, &session.transcript()).expect("Unable to solve");
+    assert_eq!(replayed, Answer::from(session.total()));
+}
+
+#[test]
+fn tournament_is_reproducible_from_its_seed() {
+    let rules = Ruleset::rock_paper_scissors();
+    let strategies = Strategy::defaults(&rules);
+    let standings = |seed| -> Vec<(String, u64)> {
+        Tournament::run(&rules, &strategies, 200, seed).standings.into_iter()
+            .map(|standing| (standing.name, standing.points))
+            .collect()
+    };
+    assert_eq!(standings(42), standings(42));
+
+    let guide = concat!(env!("CARGO_MANIFEST_DIR"), "/test.txt");
+    assert!(matches!(Strategy::parse(guide, &rules), Ok(Strategy::Replay { .. })));
+    assert!(matches!(Strategy::parse(&format!("replay:{guide}"), &rules), Ok(Strategy::Replay { .. })));
+    match Strategy::parse("wsl", &rules) {
+        Err(error) => assert!(error.to_string().starts_with("Invalid argument: wsl, expecting")),
+        Ok(strategy) => panic!("expected an error, got {}", strategy.name(&rules)),
+    }
+
+    let tournament = Tournament::run(&rules, &strategies, 200, 42);
+    for standing in &tournament.standings {
+        assert_eq!(standing.match_wins + standing.match_draws + standing.match_losses, strategies.len() - 1);
+        assert_eq!(standing.round_wins + standing.round_draws + standing.round_losses, 200 * (strategies.len() - 1));
+    }
+}
+
+#[test]
+fn scoring_overrides_default_points() {
+    let rules = Ruleset::rock_paper_scissors();
+    let default = rules.clone().with_scoring(&Scoring::default()).expect("Unable to score");
+    assert_eq!(default.solve_str(Stage::One, GUIDE).ok(), Puzzle.solve_str(Stage::One, GUIDE).ok());
+
+    let scoring = Scoring::from_points("draw=1,victory=2,Rock=10").expect("Invalid points");
+    let rules = rules.with_scoring(&scoring).expect("Unable to score");
+    assert_eq!(rules.solve_str(Stage::One, GUIDE).expect("Unable to solve"), Answer::from(18u32));
+}
+
+#[test]
+fn trace_breaks_down_the_total_score() {
+    let game = game("A Y\nA Y\nB X\nC Z\nB Z", GameMode::Simple);
+    let trace = game.trace();
+
+    assert_eq!(trace.total_score(), game.total_score());
+    assert_eq!(trace.count(RoundResult::Victory), 3);
+    assert_eq!(trace.longest_streak(RoundResult::Victory), 2);
+    assert_eq!(trace.longest_streak(RoundResult::Defeat), 1);
+    let points: Vec<u32> = trace.points_by_move().iter().map(|points| points.points).collect();
+    assert_eq!(points, [1, 16, 15]);
+    let csv = trace.to_csv();
+    assert_eq!(csv.lines().nth(1), Some("1,Rock,Paper,victory,2,6,8"));
+    assert_eq!(csv.lines().count(), 6);
+    assert!(csv.lines().all(|line| line.split(',').count() == 7));
+    assert!(trace.summary().to_string().ends_with("Total score: 32\n"));
+}
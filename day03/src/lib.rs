@@ -0,0 +1,187 @@
+use std::fmt;
+use std::ops::Range;
+
+use aoc_common::{Answer, Diagnostic, Error, Reason, Solver, Stage};
+
+
+type Item = char;
+const ITEM_LOWER_BASE: u32 = 97 - 1;
+const ITEM_UPPER_BASE: u32 = 65 - 27;
+
+pub fn item_value(item: Item) -> u32 {
+    let codepoint: u32 = item.into();
+    if item.is_lowercase() {
+        codepoint - ITEM_LOWER_BASE
+    } else {
+        codepoint - ITEM_UPPER_BASE
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    InvalidItem { item: Item, column: usize },
+    OddItemsCount { count: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidItem { item, .. } => write!(f, "Invalid item '{item}', expecting a letter"),
+            Self::OddItemsCount { count } => write!(f, "Odd number of items ({count}), compartments must be the same size"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Reason for ParseError {
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Self::InvalidItem { item, column } => Some(*column..*column + item.len_utf8()),
+            Self::OddItemsCount { count } => Some(0..*count),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Compartment {
+    content: String,
+}
+
+impl Compartment {
+
+    pub fn new(content: &str) -> Compartment {
+        Compartment { content: String::from(content) }
+    }
+
+    pub fn find_common_item(&self, other: &Compartment) -> Option<Item> {
+        self.content.chars().find(|c| other.content.contains(*c))
+    }
+}
+
+#[derive(Clone)]
+pub struct Rucksack {
+    compartment: [Compartment; 2],
+}
+
+impl Rucksack {
+
+    pub fn from(content: &str) -> Result<Rucksack, ParseError> {
+        if let Some((column, item)) = content.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            return Err(ParseError::InvalidItem { item, column });
+        }
+        let items_count = content.len();
+        if !items_count.is_multiple_of(2) {
+            return Err(ParseError::OddItemsCount { count: items_count });
+        };
+        let half_items_count = items_count / 2;
+        let rucksack = Rucksack {
+            compartment: [
+                Compartment::new(&content[0..half_items_count]),
+                Compartment::new(&content[half_items_count..]),
+            ],
+        };
+        Ok(rucksack)
+    }
+
+    pub fn find_common_item(&self) -> Option<Item> {
+        self.compartment[0].find_common_item(&self.compartment[1])
+    }
+
+    pub fn iter_all_items(&self) -> impl Iterator<Item = Item> + '_ {
+        self.compartment[0].content.chars().chain(self.compartment[1].content.chars())
+    }
+}
+
+pub struct ElfGroup {
+    rucksacks: Vec<Rucksack>
+}
+
+impl ElfGroup {
+
+    pub fn new(rucksacks: Vec<Rucksack>) -> ElfGroup {
+        ElfGroup { rucksacks }
+    }
+
+    pub fn find_common_item(&self) -> Option<Item> {
+        for item in self.rucksacks[0].iter_all_items() {
+            let is_common = self.rucksacks.iter().all(|rucksack| {
+                rucksack.iter_all_items().any(|other_item| other_item == item)
+            });
+            if is_common { return Some(item) }
+        };
+        None
+    }
+}
+
+pub struct ElvesColony {
+    elf_groups: Vec<ElfGroup>,
+}
+
+impl ElvesColony {
+
+    pub fn from(lines: impl Iterator<Item = String>, group_size: usize) -> Result<ElvesColony, Diagnostic> {
+        let mut instance = ElvesColony {
+            elf_groups: Vec::new(),
+        };
+        let mut rucksacks = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let rucksack = Rucksack::from(&line)
+                .map_err(|error| Diagnostic::new(index + 1, &line, error))?;
+            rucksacks.push(rucksack)
+        }
+
+        for group_rucksacks in rucksacks.chunks(group_size) {
+            let elf_group = ElfGroup::new(group_rucksacks.to_vec());
+            instance.elf_groups.push(elf_group);
+        }
+
+        Ok(instance)
+    }
+
+    pub fn common_items_sum(&self) -> u32 {
+        let mut result = 0;
+        for elf_group in self.elf_groups.iter() {
+            for rucksack in elf_group.rucksacks.iter() {
+                if let Some(item) = rucksack.find_common_item() {
+                    let item_val = item_value(item);
+                    // println!("{item} = {item_val}");
+                    result += item_val;
+                }
+            }
+        }
+        result
+    }
+
+    pub fn badges_sum(&self) -> Option<u32> {
+        self.elf_groups.iter().try_fold(0, |acc, elf_group| {
+            let item = elf_group.find_common_item()?;
+            let value = item_value(item);
+            Some(acc + value)
+        })
+    }
+}
+
+
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    type Input = ElvesColony;
+
+    fn parse(&self, stage: Stage, lines: impl Iterator<Item = String>) -> Result<ElvesColony, Error> {
+        let group_size = match stage {
+            Stage::One => 1,
+            Stage::Two => 3,
+        };
+        Ok(ElvesColony::from(lines, group_size)?)
+    }
+
+    fn part1(&self, colony: ElvesColony) -> Result<Answer, Error> {
+        Ok(colony.common_items_sum().into())
+    }
+
+    fn part2(&self, colony: ElvesColony) -> Result<Answer, Error> {
+        let sum = colony.badges_sum().ok_or("Unable to find a common item in all groups")?;
+        Ok(sum.into())
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+use std::ops::Range;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use aoc_common::{Answer, Diagnostic, Error, Reason, Solver, Stage};
+
+
+type SectionID = i32;
+type SectionRange = Range<SectionID>;
+
+
+pub fn are_section_ranges_fully_overlapping(lhs: &SectionRange, rhs: &SectionRange) -> bool {
+    lhs.start >= rhs.start && lhs.end <= rhs.end ||
+    rhs.start >= lhs.start && rhs.end <= lhs.end
+}
+
+pub fn are_section_ranges_partially_overlapping(lhs: &SectionRange, rhs: &SectionRange) -> bool {
+    are_section_ranges_fully_overlapping(lhs, rhs) ||
+    rhs.contains(&lhs.start) || rhs.contains(&lhs.end) ||
+    lhs.contains(&rhs.start) || lhs.contains(&rhs.end)
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    InvalidSections,
+    InvalidSectionId { span: Range<usize> },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSections => write!(f, "Expecting two section ranges such as '2-4,6-8'"),
+            Self::InvalidSectionId { .. } => write!(f, "Section id out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Reason for ParseError {
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Self::InvalidSections => None,
+            Self::InvalidSectionId { span } => Some(span.clone()),
+        }
+    }
+}
+
+pub struct ElvesPair {
+    first: SectionRange,
+    second: SectionRange,
+}
+
+impl ElvesPair {
+
+    pub fn from(line: &str) -> Result<ElvesPair, ParseError> {
+        static SECTIONS_LINE_REGEX: Lazy<regex::Regex> = Lazy::new(|| {
+            Regex::new(r"(?P<s1start>\d+)-(?P<s1end>\d+),(?P<s2start>\d+)-(?P<s2end>\d+)")
+                .expect("Unable to create the sections line regex")
+        });
+
+        let captures = SECTIONS_LINE_REGEX.captures(line).ok_or(ParseError::InvalidSections)?;
+        let section_id = |name| -> Result<SectionID, ParseError> {
+            let id = captures.name(name).ok_or(ParseError::InvalidSections)?;
+            id.as_str().parse().map_err(|_| ParseError::InvalidSectionId { span: id.range() })
+        };
+        let s1start = section_id("s1start")?;
+        let s1end = section_id("s1end")?;
+        let s2start = section_id("s2start")?;
+        let s2end = section_id("s2end")?;
+
+        let first = s1start..s1end;
+        let second = s2start..s2end;
+
+        Ok(ElvesPair { first, second })
+    }
+
+    pub fn is_fully_overlapping(&self) -> bool {
+        are_section_ranges_fully_overlapping(&self.first, &self.second)
+    }
+
+    pub fn is_partially_overlapping(&self) -> bool {
+        are_section_ranges_partially_overlapping(&self.first, &self.second)
+    }
+}
+
+
+pub struct ElvesColony {
+    groups: Vec<ElvesPair>
+}
+
+impl ElvesColony {
+
+    pub fn from(lines: impl Iterator<Item = String>) -> Result<ElvesColony, Diagnostic> {
+        let mut groups = Vec::new();
+        for (index, line) in lines.enumerate() {
+            let pair = ElvesPair::from(&line)
+                .map_err(|error| Diagnostic::new(index + 1, &line, error))?;
+            groups.push(pair);
+        }
+        Ok(ElvesColony { groups })
+    }
+
+    pub fn fully_overlapping_sections_count(&self) -> usize {
+        self.groups
+            .iter()
+            .filter_map(|p| p.is_fully_overlapping().then_some(()))
+            .count()
+    }
+
+    pub fn partially_overlapping_sections_count(&self) -> usize {
+        self.groups
+            .iter()
+            .filter_map(|p| p.is_partially_overlapping().then_some(()))
+            .count()
+    }
+}
+
+
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    type Input = ElvesColony;
+
+    fn parse(&self, _stage: Stage, lines: impl Iterator<Item = String>) -> Result<ElvesColony, Error> {
+        Ok(ElvesColony::from(lines)?)
+    }
+
+    fn part1(&self, colony: ElvesColony) -> Result<Answer, Error> {
+        Ok(colony.fully_overlapping_sections_count().into())
+    }
+
+    fn part2(&self, colony: ElvesColony) -> Result<Answer, Error> {
+        Ok(colony.partially_overlapping_sections_count().into())
+    }
+}
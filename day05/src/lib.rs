@@ -0,0 +1,290 @@
+use std::fmt;
+use std::ops::Range;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use aoc_common::{Answer, Diagnostic, Error, Reason, Solver, Stage};
+
+
+type Crate = char;
+
+
+#[derive(Debug)]
+pub enum ParseError {
+    MissingStacksCount,
+    CrateOutsideStacks { span: Range<usize>, stacks_count: usize },
+    InvalidMove,
+    InvalidNumber { span: Range<usize> },
+    InvalidStackNumber { span: Range<usize> },
+    UnknownStack { span: Range<usize>, stacks_count: usize },
+    NotEnoughCrates { span: Range<usize>, available: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingStacksCount => write!(f, "Expecting the stack numbers below the crates schema"),
+            Self::CrateOutsideStacks { stacks_count, .. } => write!(f, "Crate outside of the {stacks_count} stacks"),
+            Self::InvalidMove => write!(f, "Expecting a move such as 'move 1 from 2 to 1'"),
+            Self::InvalidNumber { .. } => write!(f, "Number out of range"),
+            Self::InvalidStackNumber { .. } => write!(f, "Stack numbers start at 1"),
+            Self::UnknownStack { stacks_count, .. } => write!(f, "No such stack, there are {stacks_count} stacks"),
+            Self::NotEnoughCrates { available, .. } => write!(f, "The stack only holds {available} crates"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Reason for ParseError {
+    fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Self::MissingStacksCount | Self::InvalidMove => None,
+            Self::CrateOutsideStacks { span, .. }
+            | Self::InvalidNumber { span }
+            | Self::InvalidStackNumber { span }
+            | Self::UnknownStack { span, .. }
+            | Self::NotEnoughCrates { span, .. } => Some(span.clone()),
+        }
+    }
+}
+
+
+pub fn crate_from_str(s: &str) -> Option<Crate> {
+    static CRATE_REGEX: Lazy<regex::Regex> = Lazy::new(|| {
+        Regex::new(r"\[(?P<id>\w)\]\s*")
+            .expect("Unable to create the crates regex")
+    });
+    let id = CRATE_REGEX.captures(s)?.name("id")?.as_str();
+    if id.len() != 1 { return None };
+    id.chars().next()
+}
+
+
+type CratesStack = Vec<Crate>;
+
+pub struct CratesStock {
+
+    stock: Vec<CratesStack>
+}
+
+impl CratesStock {
+
+    /// `schema_buffer` holds the schema lines bottom-up, starting with the stack numbers.
+    pub fn from(schema_buffer: &[String]) -> Result<CratesStock, Diagnostic> {
+        let diagnostic = |index: usize, error| {
+            Diagnostic::new(schema_buffer.len() - index, &schema_buffer[index], error)
+        };
+        let mut it = schema_buffer.iter();
+        let stacks_count: usize = it.next()
+            .and_then(|numbers| {
+                numbers.split(' ')
+                    .filter_map(|x| {
+                        x.parse().ok()
+                    })
+                    .next_back()
+            })
+            .ok_or_else(|| diagnostic(0, ParseError::MissingStacksCount))?;
+
+        let mut stock = Vec::<CratesStack>::new();
+        for _ in 0..stacks_count {
+            stock.push(CratesStack::new());
+        }
+
+        for (line_index, line) in it.enumerate() {
+            let mut current = line.as_str();
+            let mut index = 0;
+            while !current.is_empty() {
+                let (chunk, rest) = current.split_at(std::cmp::min(4, current.len()));
+                if let Some(crate_) = crate_from_str(chunk) {
+                    let Some(stack) = stock.get_mut(index) else {
+                        let span = index * 4..index * 4 + chunk.trim_end().len();
+                        return Err(diagnostic(line_index + 1, ParseError::CrateOutsideStacks { span, stacks_count }));
+                    };
+                    stack.push(crate_);
+                }
+                index += 1;
+                current = rest;
+            }
+        }
+
+        Ok(CratesStock { stock })
+    }
+
+    pub fn stacks_count(&self) -> usize {
+        self.stock.len()
+    }
+
+    pub fn _dump(&self) {
+        for stack in self.stock.iter() {
+            for crate_ in stack {
+                print!("{}", crate_);
+            }
+            println!()
+        }
+    }
+
+    pub fn top_of_stacks(&self) -> String {
+        self.stock
+            .iter()
+            .map(|stack| stack.last().unwrap_or(&' '))
+            .collect()
+    }
+
+}
+
+pub struct Move {
+    count: usize,
+    from: usize,
+    to: usize,
+    count_span: Range<usize>,
+}
+
+impl Move {
+
+    /// Reads a move between two of the `stacks_count` stacks.
+    pub fn from(line: &str, stacks_count: usize) -> Result<Move, ParseError> {
+        static MOVE_REGEX: Lazy<regex::Regex> = Lazy::new(|| {
+            Regex::new(r"move (?P<count>\d+) from (?P<from>\d+) to (?P<to>\d+)")
+                .expect("Unable to create the move regex")
+        });
+
+        let captures = MOVE_REGEX.captures(line).ok_or(ParseError::InvalidMove)?;
+        let number = |name| -> Result<(usize, Range<usize>), ParseError> {
+            let number = captures.name(name).ok_or(ParseError::InvalidMove)?;
+            let value = number.as_str().parse::<usize>()
+                .map_err(|_| ParseError::InvalidNumber { span: number.range() })?;
+            Ok((value, number.range()))
+        };
+        let stack_index = |name| -> Result<usize, ParseError> {
+            let (value, span) = number(name)?;
+            let index = value.checked_sub(1).ok_or(ParseError::InvalidStackNumber { span: span.clone() })?;
+            if index >= stacks_count {
+                return Err(ParseError::UnknownStack { span, stacks_count });
+            }
+            Ok(index)
+        };
+        let (count, count_span) = number("count")?;
+        let from = stack_index("from")?;
+        let to = stack_index("to")?;
+
+        Ok(Move { count, from, to, count_span })
+    }
+}
+
+
+/// The moves section of the input, keeping its lines to report moves that
+/// cannot be executed.
+pub struct CraneMoves {
+    moves: Vec<Move>,
+    lines: Vec<String>,
+    first_line: usize,
+}
+
+impl CraneMoves {
+
+    /// `first_line` is the 1-based line number of the first move in the input.
+    pub fn from(lines: impl Iterator<Item = String>, first_line: usize, stacks_count: usize) -> Result<CraneMoves, Diagnostic> {
+        let mut crane_moves = CraneMoves { moves: Vec::new(), lines: Vec::new(), first_line };
+        for (index, line) in lines.enumerate() {
+            let move_ = Move::from(&line, stacks_count)
+                .map_err(|error| Diagnostic::new(first_line + index, &line, error))?;
+            crane_moves.moves.push(move_);
+            crane_moves.lines.push(line);
+        }
+        Ok(crane_moves)
+    }
+
+    /// Takes the crates to move off the top of their stack, bottom crate first.
+    fn take(&self, index: usize, crates_stock: &mut CratesStock) -> Result<Vec<Crate>, Diagnostic> {
+        let move_ = &self.moves[index];
+        let stack = &mut crates_stock.stock[move_.from];
+        let start = stack.len().checked_sub(move_.count).ok_or_else(|| {
+            let error = ParseError::NotEnoughCrates { span: move_.count_span.clone(), available: stack.len() };
+            Diagnostic::new(self.first_line + index, &self.lines[index], error)
+        })?;
+        Ok(stack.split_off(start))
+    }
+}
+
+pub trait Crane {
+    fn execute(&self, moves: &CraneMoves, crates_stock: &mut CratesStock) -> Result<(), Diagnostic>;
+}
+
+#[derive(Default)]
+pub struct CrateMover9000 {
+}
+
+impl CrateMover9000 {
+
+    pub fn new() -> CrateMover9000 {
+        CrateMover9000 {}
+    }
+}
+
+impl Crane for CrateMover9000 {
+
+    fn execute(&self, crane_moves: &CraneMoves, crates_stock: &mut CratesStock) -> Result<(), Diagnostic> {
+        for (index, move_) in crane_moves.moves.iter().enumerate() {
+            let crates = crane_moves.take(index, crates_stock)?;
+            crates_stock.stock[move_.to].extend(crates.into_iter().rev());
+        }
+        Ok(())
+    }
+}
+
+
+#[derive(Default)]
+pub struct CrateMover9001 {
+}
+
+impl CrateMover9001 {
+
+    pub fn new() -> CrateMover9001 {
+        CrateMover9001 {}
+    }
+}
+
+impl Crane for CrateMover9001 {
+
+    fn execute(&self, crane_moves: &CraneMoves, crates_stock: &mut CratesStock) -> Result<(), Diagnostic> {
+        for (index, move_) in crane_moves.moves.iter().enumerate() {
+            let crates = crane_moves.take(index, crates_stock)?;
+            crates_stock.stock[move_.to].extend(crates);
+        }
+        Ok(())
+    }
+}
+
+fn execute(crane: &dyn Crane, (mut crates_stock, crane_moves): (CratesStock, CraneMoves)) -> Result<Answer, Error> {
+    crane.execute(&crane_moves, &mut crates_stock)?;
+    Ok(crates_stock.top_of_stacks().into())
+}
+
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    type Input = (CratesStock, CraneMoves);
+
+    fn parse(&self, _stage: Stage, mut lines: impl Iterator<Item = String>) -> Result<Self::Input, Error> {
+        let mut schema_buffer = Vec::<String>::new();
+        loop {
+            let line = lines.next().ok_or("Unable to find end of schema")?;
+            if line.is_empty() { break; }
+            schema_buffer.insert(0, line);
+        }
+
+        let crates_stock = CratesStock::from(&schema_buffer)?;
+        let crane_moves = CraneMoves::from(lines, schema_buffer.len() + 2, crates_stock.stacks_count())?;
+        Ok((crates_stock, crane_moves))
+    }
+
+    fn part1(&self, input: Self::Input) -> Result<Answer, Error> {
+        execute(&CrateMover9000::new(), input)
+    }
+
+    fn part2(&self, input: Self::Input) -> Result<Answer, Error> {
+        execute(&CrateMover9001::new(), input)
+    }
+}
@@ -0,0 +1,37 @@
+use aoc_common::{Error, Solver, Stage};
+
+use day05::Puzzle;
+
+
+const SCHEMA: &str = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\n";
+
+fn rejected_line(stage: Stage, moves: &str) -> (usize, String) {
+    match Puzzle.solve_str(stage, &format!("{SCHEMA}{moves}")) {
+        Err(Error::Parse(diagnostic)) => (diagnostic.line, diagnostic.reason.to_string()),
+        other => panic!("expected a diagnostic, got {other:?}"),
+    }
+}
+
+#[test]
+fn moves_to_unknown_stacks_are_rejected() {
+    let (line, reason) = rejected_line(Stage::One, "move 1 from 2 to 1\nmove 1 from 7 to 1");
+    assert_eq!(line, 7);
+    assert_eq!(reason, "No such stack, there are 3 stacks");
+    assert_eq!(rejected_line(Stage::Two, "move 1 from 1 to 4").0, 6);
+}
+
+#[test]
+fn moves_of_missing_crates_are_rejected() {
+    for stage in [Stage::One, Stage::Two] {
+        let (line, reason) = rejected_line(stage, "move 1 from 2 to 1\nmove 3 from 2 to 1");
+        assert_eq!(line, 7);
+        assert_eq!(reason, "The stack only holds 2 crates");
+    }
+}
+
+#[test]
+fn moves_onto_the_same_stack_keep_it() {
+    let input = format!("{SCHEMA}move 2 from 2 to 2");
+    assert_eq!(Puzzle.solve_str(Stage::One, &input).expect("Unable to solve").to_string(), "NCP");
+    assert_eq!(Puzzle.solve_str(Stage::Two, &input).expect("Unable to solve").to_string(), "NDP");
+}
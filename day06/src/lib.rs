@@ -0,0 +1,88 @@
+use std::fmt;
+
+use itertools::Itertools;
+
+use aoc_common::{Answer, Diagnostic, Error, Reason, Solver, Stage};
+
+
+#[derive(Debug)]
+pub enum MarkerError {
+    NoPacketStart { marker_size: usize },
+    NoMessageStart { marker_size: usize },
+}
+
+impl fmt::Display for MarkerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoPacketStart { marker_size } =>
+                write!(f, "No start-of-packet marker of {marker_size} different characters"),
+            Self::NoMessageStart { marker_size } =>
+                write!(f, "No start-of-message marker of {marker_size} different characters"),
+        }
+    }
+}
+
+impl std::error::Error for MarkerError {}
+
+impl Reason for MarkerError {}
+
+
+pub fn is_marker_valid(marker: &[char]) -> bool {
+    for couple in marker.iter().combinations(2) {
+        if couple[0] == couple[1] {
+            return false;
+        }
+    }
+    true
+}
+
+pub fn data_start_index(raw_buffer: &str, start_marker_size: usize) -> Option<usize> {
+    let mut marker = vec![raw_buffer.chars().next()?; start_marker_size];
+
+    for (i, c) in raw_buffer.chars().enumerate() {
+        marker.rotate_left(1);
+        marker[start_marker_size - 1] = c;
+        if is_marker_valid(&marker) {
+            return Some(i + 1);
+        }
+    }
+
+    None
+}
+
+fn start_indices(datastreams: &[String], packet_marker_size: usize, message_marker_size: Option<usize>) -> Result<Answer, Error> {
+    let mut indices = Vec::new();
+    for (index, line) in datastreams.iter().enumerate() {
+        let diagnostic = |error| Diagnostic::new(index + 1, line, error);
+        let packet_start_index = data_start_index(line.as_str(), packet_marker_size)
+            .ok_or_else(|| diagnostic(MarkerError::NoPacketStart { marker_size: packet_marker_size }))?;
+        let Some(msg_start_idx) = message_marker_size else {
+            indices.push(packet_start_index);
+            continue;
+        };
+        let packet = &line[packet_start_index..];
+        let start_index = data_start_index(packet, msg_start_idx)
+            .ok_or_else(|| diagnostic(MarkerError::NoMessageStart { marker_size: msg_start_idx }))?;
+        indices.push(packet_start_index + start_index);
+    }
+
+    Ok(indices.into())
+}
+
+pub struct Puzzle;
+
+impl Solver for Puzzle {
+    type Input = Vec<String>;
+
+    fn parse(&self, _stage: Stage, lines: impl Iterator<Item = String>) -> Result<Vec<String>, Error> {
+        Ok(lines.collect())
+    }
+
+    fn part1(&self, datastreams: Vec<String>) -> Result<Answer, Error> {
+        start_indices(&datastreams, 4, None)
+    }
+
+    fn part2(&self, datastreams: Vec<String>) -> Result<Answer, Error> {
+        start_indices(&datastreams, 4, Some(14))
+    }
+}